@@ -1,4 +1,5 @@
 use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
 
 fn moves(c: &mut Criterion) {
     c.bench_function("initial positions", |b| {
@@ -9,6 +10,7 @@ fn moves(c: &mut Criterion) {
                 blk: checkers::Board::new(0b0000_0000_0000_0000_0000_1111_1111_1111),
                 red: checkers::Board::new(0b1111_1111_1111_0000_0000_0000_0000_0000),
                 king: checkers::Board::new(0b0000_0000_0000_0000_0000_0000_0000_0000),
+                passed: false,
             };
 
             let _v: Vec<checkers::Move> = game.moves().collect();
@@ -23,6 +25,7 @@ fn moves(c: &mut Criterion) {
                 blk: checkers::Board::new(0b0000_0010_0000_0100_0000_0000_1111_0000),
                 red: checkers::Board::new(0b1010_0000_0101_0000_1010_0000_0000_0000),
                 king: checkers::Board::new(0b0010_0000_0001_0000_0000_0000_0010_0000),
+                passed: false,
             };
 
             let _v: Vec<checkers::Move> = game.moves().collect();
@@ -30,5 +33,81 @@ fn moves(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, moves);
+/// generate() counts every move without collecting them, so only the generation is measured.
+fn generate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generate");
+
+    let initial = checkers::Game::initial();
+    group.bench_function("initial positions", |b| {
+        b.iter(|| black_box(&initial).moves().count());
+    });
+
+    let complex = checkers::Game {
+        side: checkers::Player::BLK,
+        jumping: None,
+        blk: checkers::Board::new(0b0000_0010_0000_0100_0000_0000_1111_0000),
+        red: checkers::Board::new(0b1010_0000_0101_0000_1010_0000_0000_0000),
+        king: checkers::Board::new(0b0010_0000_0001_0000_0000_0000_0010_0000),
+        passed: false,
+    };
+    group.bench_function("complex positions", |b| {
+        b.iter(|| black_box(&complex).moves().count());
+    });
+
+    let jumps = checkers::Game {
+        side: checkers::Player::BLK,
+        jumping: None,
+        blk: checkers::Board::new(0b0000_0000_0000_0000_0000_0100_1010_0000),
+        red: checkers::Board::new(0b0000_0000_0000_0110_0101_0000_0000_0000),
+        king: checkers::Board::new(0b0000_0000_0000_0000_0000_0000_0010_0000),
+        passed: false,
+    };
+    group.bench_function("jump positions", |b| {
+        b.iter(|| black_box(&jumps).moves().map(|m| black_box(m.dst())).last());
+    });
+
+    group.finish();
+}
+
+/// walk_apply() counts leaf positions by cloning the game with apply().
+fn walk_apply(game: &checkers::Game, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    game.moves()
+        .map(|m| walk_apply(&game.apply(&m), depth - 1))
+        .sum()
+}
+
+/// walk_make() counts leaf positions by updating the game in place with make()/unmake().
+fn walk_make(game: &mut checkers::Game, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+    let mut count = 0;
+    for m in game.moves() {
+        let undo = game.make(&m);
+        count += walk_make(game, depth - 1);
+        game.unmake(&m, &undo);
+    }
+    count
+}
+
+fn tree(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree walk");
+
+    group.bench_function("apply", |b| {
+        let game = checkers::Game::initial();
+        b.iter(|| walk_apply(&game, 6));
+    });
+
+    group.bench_function("make/unmake", |b| {
+        let mut game = checkers::Game::initial();
+        b.iter(|| walk_make(&mut game, 6));
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, moves, generate, tree);
 criterion_main!(benches);
@@ -0,0 +1,112 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+
+use checkers::suite;
+use checkers::{Options, Searcher};
+
+/// run() prints the node counts of the options on the suite once, since they don't depend
+/// on the timing, and measures the time to search the whole suite.
+fn run(c: &mut Criterion, group: &str, configs: &[(&str, Options)]) {
+    let mut group = c.benchmark_group(group);
+    let games = suite::games();
+
+    for &(name, options) in configs {
+        let mut total = 0;
+        for (position, game) in &games {
+            let nodes = Searcher::new(options).search(game).nodes;
+            println!("{} {}: {} nodes", name, position, nodes);
+            total += nodes;
+        }
+        println!("{} total: {} nodes", name, total);
+
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for (_, game) in &games {
+                    black_box(Searcher::new(options).search(black_box(game)));
+                }
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// ordering() compares searches with and without move ordering.
+fn ordering(c: &mut Criterion) {
+    let ordered = Options {
+        depth: 8,
+        pvs: false,
+        reductions: false,
+        futility: false,
+        ..Options::default()
+    };
+    let unordered = Options {
+        ordering: false,
+        ..ordered
+    };
+    run(
+        c,
+        "ordering",
+        &[("unordered", unordered), ("ordered", ordered)],
+    );
+}
+
+/// pruning() compares each pruning option with the exact search.
+fn pruning(c: &mut Criterion) {
+    let exact = Options {
+        depth: 8,
+        pvs: false,
+        reductions: false,
+        futility: false,
+        ..Options::default()
+    };
+    run(
+        c,
+        "pruning",
+        &[
+            ("exact", exact),
+            ("pvs", Options { pvs: true, ..exact }),
+            (
+                "reductions",
+                Options {
+                    reductions: true,
+                    ..exact
+                },
+            ),
+            (
+                "futility",
+                Options {
+                    futility: true,
+                    ..exact
+                },
+            ),
+            (
+                "all",
+                Options {
+                    depth: 8,
+                    ..Options::default()
+                },
+            ),
+        ],
+    );
+}
+
+/// threads() compares Lazy SMP searches with different numbers of threads.
+fn threads(c: &mut Criterion) {
+    let configs: Vec<(String, Options)> = [1, 2, 4]
+        .into_iter()
+        .map(|threads| {
+            let options = Options {
+                depth: 10,
+                threads,
+                ..Options::default()
+            };
+            (format!("{} threads", threads), options)
+        })
+        .collect();
+    let configs: Vec<(&str, Options)> = configs.iter().map(|(n, o)| (n.as_str(), *o)).collect();
+    run(c, "threads", &configs);
+}
+
+criterion_group!(benches, ordering, pruning, threads);
+criterion_main!(benches);
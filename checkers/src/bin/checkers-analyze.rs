@@ -0,0 +1,87 @@
+//! checkers-analyze prints the best moves of a game with their scores and principal variations.
+//!
+//! usage: checkers-analyze [--depth N] [--lines N] [--red] [DIAGRAM_FILE]
+//!
+//! the game is read from a diagram file like Game::from_diagram() accepts, black to move
+//! unless --red is given. without a file, the initial game is analyzed.
+
+use std::env;
+use std::fs;
+use std::process;
+
+use checkers::search::{Options, Score, Searcher, MAX_PLY, WIN};
+use checkers::{Game, GameTree, Player};
+
+struct Args {
+    depth: u32,
+    lines: usize,
+    side: Player,
+    file: Option<String>,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut args = Args {
+        depth: 8,
+        lines: 3,
+        side: Player::BLK,
+        file: None,
+    };
+    let mut it = env::args().skip(1);
+    while let Some(arg) = it.next() {
+        let mut number = |name: &str| {
+            it.next()
+                .and_then(|v| v.parse().ok())
+                .ok_or(format!("{} needs a number", name))
+        };
+        match arg.as_str() {
+            "--depth" => args.depth = number("--depth")?,
+            "--lines" => args.lines = number("--lines")? as usize,
+            "--red" => args.side = Player::RED,
+            _ if arg.starts_with("--") => return Err(format!("unknown option {}", arg)),
+            _ => args.file = Some(arg),
+        }
+    }
+    Ok(args)
+}
+
+fn load(args: &Args) -> Result<Game, String> {
+    let Some(file) = &args.file else {
+        return Ok(Game::initial());
+    };
+    let text = fs::read_to_string(file).map_err(|e| format!("{}: {}", file, e))?;
+    Game::from_diagram(args.side, None, &text).map_err(|e| format!("{}: {}", file, e))
+}
+
+/// score() formats the score in pones, or as a win or loss in plies.
+fn score(s: Score) -> String {
+    match s {
+        _ if s > WIN - MAX_PLY => format!("win in {}", WIN - s),
+        _ if s < -WIN + MAX_PLY => format!("loss in {}", WIN + s),
+        _ => format!("{:+.2}", s as f64 / 100.0),
+    }
+}
+
+fn main() {
+    let args = parse_args().unwrap_or_else(|e| {
+        eprintln!("checkers-analyze: {}", e);
+        eprintln!("usage: checkers-analyze [--depth N] [--lines N] [--red] [DIAGRAM_FILE]");
+        process::exit(2);
+    });
+    let game = load(&args).unwrap_or_else(|e| {
+        eprintln!("checkers-analyze: {}", e);
+        process::exit(1);
+    });
+
+    let mut searcher = Searcher::new(Options {
+        depth: args.depth,
+        ..Options::default()
+    });
+    println!("{}", game);
+    for (i, line) in searcher.analyze(&game, args.lines).iter().enumerate() {
+        let mut tree = GameTree::new(game.clone());
+        for m in &line.pv {
+            tree.play(m).expect("principal variations are legal");
+        }
+        println!("{}. {:>10}  {}", i + 1, score(line.score), tree.pdn());
+    }
+}
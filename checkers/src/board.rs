@@ -3,6 +3,9 @@ use derive_more::{
     ShrAssign,
 };
 
+use std::fmt::{Display, Formatter, Result};
+
+use crate::dir::Dir;
 use crate::pos::Pos;
 
 #[derive(
@@ -28,6 +31,38 @@ use crate::pos::Pos;
 pub struct Board(u32);
 
 impl Board {
+    /// FULL has every cell of the board.
+    pub const FULL: Board = Board(!0);
+
+    /// ROWS are the rows of the board from black's home row (internal y = 0) to red's home row.
+    pub const ROWS: [Board; 8] = [
+        Board(0x0000_000F),
+        Board(0x0000_00F0),
+        Board(0x0000_0F00),
+        Board(0x0000_F000),
+        Board(0x000F_0000),
+        Board(0x00F0_0000),
+        Board(0x0F00_0000),
+        Board(0xF000_0000),
+    ];
+
+    /// BLK_PROMOTION is the row where black pones are crowned.
+    pub const BLK_PROMOTION: Board = Self::ROWS[7];
+    /// RED_PROMOTION is the row where red pones are crowned.
+    pub const RED_PROMOTION: Board = Self::ROWS[0];
+    /// PROMOTION is the rows where any pone is crowned.
+    pub const PROMOTION: Board = Board(Self::BLK_PROMOTION.0 | Self::RED_PROMOTION.0);
+
+    /// CENTER is the four cells in the middle of the board.
+    pub const CENTER: Board = Board(0x0006_6000);
+
+    /// LEFT_EDGE is the most left column on the graphical board.
+    pub const LEFT_EDGE: Board = Board(0x0808_0808);
+    /// RIGHT_EDGE is the most right column on the graphical board.
+    pub const RIGHT_EDGE: Board = Board(0x1010_1010);
+    /// EDGES is every cell on the edge of the board.
+    pub const EDGES: Board = Board(Self::LEFT_EDGE.0 | Self::RIGHT_EDGE.0 | Self::PROMOTION.0);
+
     pub const fn new(bits: u32) -> Board {
         Board(bits)
     }
@@ -36,25 +71,126 @@ impl Board {
         Board(0)
     }
 
+    /// bits() returns the raw bits of this board.
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// is_empty() checks this board has no active position.
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// count() returns the number of active positions.
+    pub fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// contains() checks the position is active on this board.
+    pub fn contains(self, pos: Pos) -> bool {
+        pos.is(self)
+    }
+
+    /// set() activates the position.
+    pub fn set(&mut self, pos: Pos) {
+        *self |= pos.board();
+    }
+
+    /// clear() deactivates the position.
+    pub fn clear(&mut self, pos: Pos) {
+        *self &= !pos.board();
+    }
+
+    /// shift() moves every active position one cell to the direction.
+    /// positions moving out of the board are dropped.
+    pub fn shift(self, dir: Dir) -> Board {
+        dir.apply(self)
+    }
+
+    /// rotate() turns the board 180 degrees, so each player's home row swaps.
+    /// it's the only symmetry of the board: mirroring left-right would move pieces onto
+    /// unused cells, because the single corner and the double corner are different.
+    pub const fn rotate(self) -> Board {
+        Board(self.0.reverse_bits())
+    }
+
     /// actives() iterate all active positions.
-    pub fn actives(self) -> impl Iterator<Item = Pos> {
-        let mut bits = self.0;
-        let mut shifted = 0u8;
-        std::iter::from_fn(move || {
-            if bits == 0 {
-                return None;
+    pub fn actives(self) -> Actives {
+        Actives {
+            bits: self.0,
+            shifted: 0,
+        }
+    }
+}
+
+/// Actives iterates active positions of a Board in the order of Pos.
+#[derive(Debug, Clone)]
+pub struct Actives {
+    bits: u32,
+    shifted: u8,
+}
+
+impl Iterator for Actives {
+    type Item = Pos;
+
+    fn next(&mut self) -> Option<Pos> {
+        if self.bits == 0 {
+            return None;
+        }
+        let shift = self.bits.trailing_zeros() as u8;
+        self.bits >>= shift;
+        self.bits &= !1u32;
+        self.shifted += shift;
+        Some(Pos::raw(self.shifted))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let n = self.bits.count_ones() as usize;
+        (n, Some(n))
+    }
+}
+
+impl ExactSizeIterator for Actives {}
+
+impl IntoIterator for Board {
+    type Item = Pos;
+    type IntoIter = Actives;
+
+    fn into_iter(self) -> Actives {
+        self.actives()
+    }
+}
+
+impl FromIterator<Pos> for Board {
+    fn from_iter<I: IntoIterator<Item = Pos>>(iter: I) -> Board {
+        iter.into_iter()
+            .fold(Board::empty(), |board, pos| board | pos.board())
+    }
+}
+
+impl Display for Board {
+    /// fmt() renders the graphical board, `1` for active cells, `0` for inactive cells and
+    /// `_` for unused cells, black's home row at the bottom.
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        for y in 0..8 {
+            if y != 0 {
+                writeln!(f)?;
             }
-            let shift = bits.trailing_zeros() as u8;
-            bits >>= shift;
-            bits &= !1u32;
-            shifted += shift;
-            Some(Pos::raw(shifted))
-        })
+            for x in 0..8 {
+                let c = match Pos::graphical(x, y) {
+                    Some(pos) if pos.is(*self) => '1',
+                    Some(_) => '0',
+                    None => '_',
+                };
+                write!(f, "{}", c)?;
+            }
+        }
+        Ok(())
     }
 }
 
 #[cfg(test)]
-pub mod testutil {
+pub(crate) mod testutil {
     use super::*;
 
     use crate::pos::Pos;
@@ -71,9 +207,8 @@ pub mod testutil {
                     '1' => Some(pos),
                     _ => None,
                 });
-                match pos {
-                    Some(pos) => board |= pos.board(),
-                    None => (),
+                if let Some(pos) = pos {
+                    board |= pos.board();
                 }
             }
         }
@@ -163,4 +298,101 @@ pub mod tests {
             assert_eq!(actual, expect);
         }
     }
+
+    #[test]
+    fn display_renders_graphical_board() {
+        let expect = unindent::unindent(
+            r"
+            _1_0_0_0
+            1_0_0_0_
+            _0_0_0_0
+            0_0_0_0_
+            _0_0_1_0
+            0_0_0_0_
+            _0_1_0_0
+            0_0_0_1_",
+        );
+        let target = testutil::board(&expect);
+
+        assert_eq!(expect, target.to_string());
+    }
+
+    #[test]
+    fn set_clear_and_contains() {
+        let pos = Pos::new(2, 5);
+        let mut target = Board::empty();
+
+        target.set(pos);
+        assert_eq!(true, target.contains(pos));
+        assert_eq!(1, target.count());
+
+        target.clear(pos);
+        assert_eq!(false, target.contains(pos));
+        assert_eq!(true, target.is_empty());
+    }
+
+    #[test]
+    fn collect_from_positions() {
+        let positions = [Pos::raw(0), Pos::raw(6), Pos::raw(31)];
+
+        let target: Board = positions.into_iter().collect();
+
+        assert_eq!(
+            Board::new(0b1000_0000_0000_0000_0000_0000_0100_0001),
+            target
+        );
+        assert_eq!(positions.to_vec(), target.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn named_masks() {
+        let cases = [
+            (
+                "Promotion rows",
+                Board::PROMOTION,
+                r"
+                    _1_1_1_1
+                    0_0_0_0_
+                    _0_0_0_0
+                    0_0_0_0_
+                    _0_0_0_0
+                    0_0_0_0_
+                    _0_0_0_0
+                    1_1_1_1_
+                ",
+            ),
+            (
+                "Center",
+                Board::CENTER,
+                r"
+                    _0_0_0_0
+                    0_0_0_0_
+                    _0_0_0_0
+                    0_1_1_0_
+                    _0_1_1_0
+                    0_0_0_0_
+                    _0_0_0_0
+                    0_0_0_0_
+                ",
+            ),
+            (
+                "Edges",
+                Board::EDGES,
+                r"
+                    _1_1_1_1
+                    1_0_0_0_
+                    _0_0_0_1
+                    1_0_0_0_
+                    _0_0_0_1
+                    1_0_0_0_
+                    _0_0_0_1
+                    1_1_1_1_
+                ",
+            ),
+        ];
+
+        for (msg, actual, expect) in cases {
+            assert_eq!(testutil::board(expect), actual, "{}", msg);
+        }
+    }
 }
@@ -0,0 +1,283 @@
+use std::fmt::{self, Display, Formatter};
+
+use crate::error::DiagramError;
+use crate::game::Game;
+use crate::player::Player;
+use crate::pos::Pos;
+
+impl Game {
+    /// from_diagram() parses a text diagram of the graphical board, black's home row at the
+    /// bottom. each row has 8 cells: `_` for unused cells, `.` for empty cells,
+    /// `b`/`B` for black pone/king and `r`/`R` for red pone/king.
+    /// leading and trailing spaces and blank lines are ignored.
+    /// impossible positions are rejected with the violated invariants.
+    /// ```
+    /// use checkers::{Game, Player};
+    /// let game = Game::from_diagram(
+    ///     Player::BLK,
+    ///     None,
+    ///     "
+    ///     _._._._.
+    ///     ._._._._
+    ///     _._._._.
+    ///     ._._R_._
+    ///     _b_._._.
+    ///     ._._._._
+    ///     _._._._.
+    ///     ._._._._
+    ///     ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(2, (game.blk | game.red).count());
+    /// ```
+    pub fn from_diagram(side: Player, jumping: Option<Pos>, s: &str) -> Result<Game, DiagramError> {
+        let rows: Vec<&str> = s.lines().map(str::trim).filter(|l| !l.is_empty()).collect();
+        if rows.len() != 8 {
+            return Err(DiagramError::Rows(rows.len()));
+        }
+
+        let mut game = Game {
+            side,
+            jumping,
+            ..Default::default()
+        };
+
+        for (y, row) in rows.into_iter().enumerate() {
+            let len = row.chars().count();
+            if len != 8 {
+                return Err(DiagramError::Columns { row: y, len });
+            }
+
+            for (x, c) in row.chars().enumerate() {
+                let err = DiagramError::Char { row: y, col: x, c };
+                let pos = match (Pos::graphical(x as u8, y as u8), c) {
+                    (None, '_') => continue,
+                    (None, _) => return Err(err),
+                    (Some(_), '.') => continue,
+                    (Some(pos), _) => pos,
+                };
+                match c {
+                    'b' | 'B' => game.blk |= pos.board(),
+                    'r' | 'R' => game.red |= pos.board(),
+                    _ => return Err(err),
+                }
+                if c.is_ascii_uppercase() {
+                    game.king |= pos.board();
+                }
+            }
+        }
+
+        game.validate().map_err(DiagramError::Invalid)?;
+        Ok(game)
+    }
+
+    /// diagram() returns a pretty-printer of this game, which can show coordinates and
+    /// square numbers in addition to the plain diagram.
+    pub fn diagram(&self) -> Diagram<'_> {
+        Diagram {
+            game: self,
+            coordinates: false,
+            squares: false,
+        }
+    }
+
+    /// cell() returns the character of the position in the diagram.
+    fn cell(&self, pos: Pos) -> char {
+        let c = match () {
+            _ if pos.is(self.blk) => 'b',
+            _ if pos.is(self.red) => 'r',
+            _ => return '.',
+        };
+        if pos.is(self.king) {
+            c.to_ascii_uppercase()
+        } else {
+            c
+        }
+    }
+}
+
+impl Display for Game {
+    /// fmt() renders the same diagram from_diagram() parses.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.diagram())
+    }
+}
+
+/// Diagram is a pretty-printer of a Game.
+#[derive(Debug, Clone, Copy)]
+pub struct Diagram<'a> {
+    game: &'a Game,
+    coordinates: bool,
+    squares: bool,
+}
+
+impl<'a> Diagram<'a> {
+    /// coordinates() enables rank numbers on the left and file letters at the bottom.
+    pub fn coordinates(mut self, enabled: bool) -> Diagram<'a> {
+        self.coordinates = enabled;
+        self
+    }
+
+    /// squares() enables the standard square numbers on the empty cells.
+    pub fn squares(mut self, enabled: bool) -> Diagram<'a> {
+        self.squares = enabled;
+        self
+    }
+}
+
+impl Display for Diagram<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // square numbers need 2 characters, so every cell is widened and separated.
+        let (width, sep) = if self.squares { (2, " ") } else { (1, "") };
+
+        for y in 0..8 {
+            if y != 0 {
+                writeln!(f)?;
+            }
+            if self.coordinates {
+                write!(f, "{} ", 8 - y)?;
+            }
+            for x in 0..8 {
+                if x != 0 {
+                    write!(f, "{}", sep)?;
+                }
+                match Pos::graphical(x, y) {
+                    None => write!(f, "{:>width$}", '_')?,
+                    Some(pos) => match self.game.cell(pos) {
+                        '.' if self.squares => write!(f, "{:>width$}", pos.square())?,
+                        c => write!(f, "{:>width$}", c)?,
+                    },
+                }
+            }
+        }
+
+        if self.coordinates {
+            writeln!(f)?;
+            write!(f, "  ")?;
+            for (x, file) in ('a'..='h').enumerate() {
+                if x != 0 {
+                    write!(f, "{}", sep)?;
+                }
+                write!(f, "{:>width$}", file)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::invariant::Violation;
+    use pretty_assertions::assert_eq;
+    use unindent::unindent;
+
+    const DIAGRAM: &str = r"
+        _._._._.
+        ._._._._
+        _._._._.
+        ._._R_._
+        _b_._._.
+        ._._._._
+        _._._r_.
+        B_._._._
+    ";
+
+    #[test]
+    fn parse_and_render_round_trip() {
+        let game = Game::from_diagram(Player::RED, None, DIAGRAM).unwrap();
+
+        assert_eq!(unindent(DIAGRAM).trim_end(), game.to_string());
+        assert_eq!(
+            Ok(game.clone()),
+            Game::from_diagram(Player::RED, None, &game.to_string())
+        );
+    }
+
+    #[test]
+    fn render_with_coordinates_and_squares() {
+        let game = Game::from_diagram(Player::RED, None, DIAGRAM).unwrap();
+
+        let expect = unindent(
+            r"
+            8  _ 32  _ 31  _ 30  _ 29
+            7 28  _ 27  _ 26  _ 25  _
+            6  _ 24  _ 23  _ 22  _ 21
+            5 20  _ 19  _  R  _ 17  _
+            4  _  b  _ 15  _ 14  _ 13
+            3 12  _ 11  _ 10  _  9  _
+            2  _  8  _  7  _  r  _  5
+            1  B  _  3  _  2  _  1  _
+               a  b  c  d  e  f  g  h",
+        );
+
+        assert_eq!(
+            expect,
+            game.diagram().coordinates(true).squares(true).to_string()
+        );
+    }
+
+    #[test]
+    fn render_with_coordinates() {
+        let game = Game::from_diagram(Player::RED, None, DIAGRAM).unwrap();
+
+        let expect = unindent(
+            r"
+            8 _._._._.
+            7 ._._._._
+            6 _._._._.
+            5 ._._R_._
+            4 _b_._._.
+            3 ._._._._
+            2 _._._r_.
+            1 B_._._._
+              abcdefgh",
+        );
+
+        assert_eq!(expect, game.diagram().coordinates(true).to_string());
+    }
+
+    #[test]
+    fn parse_rejects_broken_diagrams() {
+        let cases = [
+            ("Missing row", "_._._._.\n._._._._", DiagramError::Rows(2)),
+            (
+                "Short row",
+                "_._._._.\n._._._._\n_._._._.\n._._\n_._._._.\n._._._._\n_._._._.\n._._._._",
+                DiagramError::Columns { row: 3, len: 4 },
+            ),
+            (
+                "Piece on unused cell",
+                "b._._._.\n._._._._\n_._._._.\n._._._._\n_._._._.\n._._._._\n_._._._.\n._._._._",
+                DiagramError::Char {
+                    row: 0,
+                    col: 0,
+                    c: 'b',
+                },
+            ),
+            (
+                "Unknown piece",
+                "_._._._.\n._._._._\n_._._._.\n._._._._\n_._x_._.\n._._._._\n_._._._.\n._._._._",
+                DiagramError::Char {
+                    row: 4,
+                    col: 3,
+                    c: 'x',
+                },
+            ),
+            (
+                "Uncrowned red pone on its promotion row",
+                "_._._._.\n._._._._\n_._._._.\n._._._._\n_._._._.\n._._._._\n_._._._.\nr_._._._",
+                DiagramError::Invalid(vec![Violation::UncrownedPone(Pos::raw(3).board())]),
+            ),
+        ];
+
+        for (msg, s, expect) in cases {
+            assert_eq!(
+                Err(expect),
+                Game::from_diagram(Player::BLK, None, s),
+                "{}",
+                msg
+            );
+        }
+    }
+}
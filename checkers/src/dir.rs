@@ -11,6 +11,13 @@ pub enum Dir {
 }
 
 impl Dir {
+    pub const ALL: [Dir; 4] = [
+        Dir::ForwardRight,
+        Dir::ForwardLeft,
+        Dir::BackwardLeft,
+        Dir::BackwardRight,
+    ];
+
     pub fn valid(self, p: Player, king: bool, pos: Pos) -> bool {
         self.valid_piece(p, king) && self.valid_pos(pos)
     }
@@ -21,8 +28,30 @@ impl Dir {
         ls[0].apply(target) | ls[1].apply(target)
     }
 
+    /// reverse() returns the opposite direction.
+    pub fn reverse(self) -> Dir {
+        match self {
+            Self::ForwardRight => Self::BackwardLeft,
+            Self::ForwardLeft => Self::BackwardRight,
+            Self::BackwardLeft => Self::ForwardRight,
+            Self::BackwardRight => Self::ForwardLeft,
+        }
+    }
+
+    /// step() returns the next cell to this direction, or None at the edge of the board.
     #[inline(always)]
-    fn valid_piece(self, p: Player, king: bool) -> bool {
+    pub fn step(self, pos: Pos) -> Option<Pos> {
+        Self::NEIGHBOURS[self as usize][pos.index()]
+    }
+
+    /// jump() returns the landing cell of a jump to this direction, or None if it's off the board.
+    #[inline(always)]
+    pub fn jump(self, pos: Pos) -> Option<Pos> {
+        Self::JUMPS[self as usize][pos.index()]
+    }
+
+    #[inline(always)]
+    pub(crate) fn valid_piece(self, p: Player, king: bool) -> bool {
         match (self, p, king) {
             (_, _, true) => true,
             (Self::ForwardRight, Player::BLK, _) => true,
@@ -35,7 +64,42 @@ impl Dir {
 
     #[inline(always)]
     fn valid_pos(self, target: Pos) -> bool {
-        let pad = target.y() % 2 == 0;
+        self.step(target).is_some()
+    }
+
+    /// NEIGHBOURS is the next cell for each direction and position.
+    const NEIGHBOURS: [[Option<Pos>; 32]; 4] = Self::table(1);
+
+    /// JUMPS is the landing cell of a jump for each direction and position.
+    const JUMPS: [[Option<Pos>; 32]; 4] = Self::table(2);
+
+    /// table() builds a lookup table of the cell `dist` cells away for each direction and position.
+    const fn table(dist: u8) -> [[Option<Pos>; 32]; 4] {
+        let mut table = [[None; 32]; 4];
+        let mut d = 0;
+        while d < 4 {
+            let mut i = 0;
+            while i < 32 {
+                let mut cur = Some(Pos::raw(i as u8));
+                let mut n = 0;
+                while n < dist {
+                    cur = match cur {
+                        Some(pos) => Self::ALL[d].neighbour(pos),
+                        None => None,
+                    };
+                    n += 1;
+                }
+                table[d][i] = cur;
+                i += 1;
+            }
+            d += 1;
+        }
+        table
+    }
+
+    /// neighbour() calculates the next cell from coordinates, it's only used to build tables.
+    const fn neighbour(self, target: Pos) -> Option<Pos> {
+        let pad = target.y().is_multiple_of(2);
         let diff = match (self, pad) {
             (Self::ForwardRight, true) => (0, 1),
             (Self::ForwardRight, false) => (-1, 1),
@@ -50,7 +114,11 @@ impl Dir {
         let mx = target.x() as i8 + diff.0;
         let my = target.y() as i8 + diff.1;
 
-        (0..4).contains(&mx) && (0..8).contains(&my)
+        if 0 <= mx && mx < 4 && 0 <= my && my < 8 {
+            Some(Pos::new(mx as u8, my as u8))
+        } else {
+            None
+        }
     }
 
     #[inline(always)]
@@ -197,6 +265,33 @@ mod tests {
         }
     }
 
+    #[test]
+    fn tables_agree_with_apply() {
+        for dir in Dir::ALL {
+            for i in 0..32 {
+                let pos = Pos::raw(i);
+
+                let first = dir.apply(pos.board());
+                let second = dir.apply(first);
+
+                assert_eq!(
+                    first.actives().next(),
+                    dir.step(pos),
+                    "step {:?} {:?}",
+                    dir,
+                    pos
+                );
+                assert_eq!(
+                    second.actives().next(),
+                    dir.jump(pos),
+                    "jump {:?} {:?}",
+                    dir,
+                    pos
+                );
+            }
+        }
+    }
+
     #[test]
     fn apply_latent_direciton() {
         let cases = [
@@ -0,0 +1,78 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::invariant::Violation;
+
+/// MoveError explains why a Move can't be applied to a Game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// there is no piece on the source cell.
+    NoPiece,
+    /// the piece on the source cell belongs to the opponent.
+    WrongSide,
+    /// another piece is in the middle of jumping, so only that piece can move.
+    NotJumpingPiece,
+    /// the move leaves the board.
+    OffBoard,
+    /// pones can't move backward.
+    WrongDirection,
+    /// the destination cell is already occupied.
+    Occupied,
+    /// a jump move must jump over an opponent piece.
+    NothingToCapture,
+    /// a capture is mandatory, but the move doesn't capture.
+    CaptureRequired,
+}
+
+impl Display for MoveError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let msg = match self {
+            Self::NoPiece => "there is no piece to move",
+            Self::WrongSide => "the piece belongs to the opponent",
+            Self::NotJumpingPiece => "another piece is in the middle of jumping",
+            Self::OffBoard => "the move leaves the board",
+            Self::WrongDirection => "pones can't move backward",
+            Self::Occupied => "the destination is occupied",
+            Self::NothingToCapture => "there is no opponent piece to jump over",
+            Self::CaptureRequired => "a capture is mandatory",
+        };
+        f.write_str(msg)
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+/// DiagramError explains why a text diagram can't be parsed into a Game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiagramError {
+    /// the diagram doesn't have 8 rows.
+    Rows(usize),
+    /// the row doesn't have 8 columns.
+    Columns { row: usize, len: usize },
+    /// the character is unknown, or a piece is placed on an unused cell.
+    Char { row: usize, col: usize, c: char },
+    /// the diagram is well-formed, but the position is impossible.
+    Invalid(Vec<Violation>),
+}
+
+impl Display for DiagramError {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Rows(n) => write!(f, "the diagram has {} rows instead of 8", n),
+            Self::Columns { row, len } => {
+                write!(f, "row {} has {} columns instead of 8", row, len)
+            }
+            Self::Char { row, col, c } => {
+                write!(f, "unexpected '{}' at row {}, column {}", c, row, col)
+            }
+            Self::Invalid(v) => {
+                write!(f, "the position is impossible:")?;
+                for violation in v {
+                    write!(f, " {};", violation)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DiagramError {}
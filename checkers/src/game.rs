@@ -1,8 +1,39 @@
 use crate::board::Board;
 use crate::dir::Dir;
-use crate::mv::Move;
+use crate::error::MoveError;
+use crate::goal::Goal;
+use crate::mv::{Move, MoveList};
 use crate::player::Player;
 use crate::pos::Pos;
+use crate::rng::Rng;
+
+/// Undo records the state make() overwrites: the previous side, jumping piece, kings and pass,
+/// and the cell of the captured piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo {
+    side: Player,
+    jumping: Option<Pos>,
+    king: Board,
+    passed: bool,
+    captured: Board,
+}
+
+/// ZOBRIST are the random keys of black pones, black kings, red pones, red kings and the
+/// jumping piece on each cell, which key() combines.
+const ZOBRIST: [[u64; 32]; 5] = {
+    let mut rng = Rng::new(0x5EED);
+    let mut keys = [[0; 32]; 5];
+    let mut i = 0;
+    while i < 5 * 32 {
+        keys[i / 32][i % 32] = rng.next_u64();
+        i += 1;
+    }
+    keys
+};
+/// ZOBRIST_SIDE is the key of black to move.
+const ZOBRIST_SIDE: u64 = Rng::new(0x51DE).next_u64();
+/// ZOBRIST_PASSED is the key of a game whose opponent has passed.
+const ZOBRIST_PASSED: u64 = Rng::new(0x9A55).next_u64();
 
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
 pub struct Game {
@@ -11,30 +42,86 @@ pub struct Game {
     pub red: Board,           // 1st player piece existence.
     pub blk: Board,           // 2nd player piece existence.
     pub king: Board,          // the piece is king or pone.
+    pub passed: bool,         // the opponent was blocked by the last move, so its turn was passed.
 }
 
 impl Game {
-    /// moves() enumerates all next move candidates.
+    /// initial() returns the starting position, 12 pones for each player and black moves first.
+    pub fn initial() -> Game {
+        Game {
+            side: Player::BLK,
+            jumping: None,
+            red: Board::new(0b1111_1111_1111_0000_0000_0000_0000_0000),
+            blk: Board::new(0b0000_0000_0000_0000_0000_1111_1111_1111),
+            king: Board::empty(),
+            passed: false,
+        }
+    }
+
+    /// moves() enumerates all next move candidates under the American rules.
+    /// a capture sequence is played one hop at a time; use turns() for whole turns under
+    /// other Rules.
     pub fn moves(&self) -> impl Iterator<Item = Move> {
-        let jumpables = self.jumpables();
-        let jumped = jumpables != Board::empty();
-        let movables = match (self.jumping, jumped) {
-            (Some(pos), true) => jumpables & pos.board(),
-            (Some(_), false) => Board::empty(),
-            (None, true) => jumpables,
-            (None, false) => self.movables(),
+        self.move_list().into_iter()
+    }
+
+    /// move_list() generates all next move candidates into a list on the stack.
+    /// sources are found for each direction at once from the bitboards, so no candidate is
+    /// validated one by one.
+    pub fn move_list(&self) -> MoveList {
+        let mut list = MoveList::new();
+
+        let (slf, opp) = match self.side {
+            Player::BLK => (self.blk, self.red),
+            Player::RED => (self.red, self.blk),
         };
+        let srcs = match self.jumping {
+            Some(pos) => pos.board() & slf,
+            None => slf,
+        };
+        let gap = self.gap();
+
+        for dir in Dir::ALL {
+            let back = dir.reverse();
+            let pieces = match dir.valid_piece(self.side, false) {
+                true => srcs,
+                false => srcs & self.king,
+            };
+            let jumpers = back.apply(back.apply(gap) & opp) & pieces;
+            for src in jumpers.actives() {
+                list.push(Move {
+                    src,
+                    dir,
+                    jump: true,
+                });
+            }
+        }
 
-        let cloned = self.clone();
+        if !list.is_empty() || self.jumping.is_some() {
+            return list;
+        }
 
-        movables
-            .actives()
-            .flat_map(move |p| Move::cands(p, jumped))
-            .filter(move |m| cloned.valid(m))
+        for dir in Dir::ALL {
+            let back = dir.reverse();
+            let pieces = match dir.valid_piece(self.side, false) {
+                true => srcs,
+                false => srcs & self.king,
+            };
+            let movers = back.apply(gap) & pieces;
+            for src in movers.actives() {
+                list.push(Move {
+                    src,
+                    dir,
+                    jump: false,
+                });
+            }
+        }
+
+        list
     }
 
     /// PROMOTION_MASK is mask for pone's promotion.
-    const PROMOTION_MASK: Board = Board::new(0b1111_0000_0000_0000_0000_0000_0000_1111);
+    pub(crate) const PROMOTION_MASK: Board = Board::PROMOTION;
 
     pub fn apply(&self, m: &Move) -> Game {
         let mut g = self.clone();
@@ -67,50 +154,242 @@ impl Game {
             g.jumping = None;
         }
 
+        g.passed = false;
         if g.moves().next().is_none() {
             g.side = !g.side;
             g.jumping = None;
+            g.passed = g.blocked(m);
         }
 
+        debug_assert_eq!(Ok(()), g.validate(), "apply {:?} on {:?}", m, self);
         g
     }
 
-    /// winner() returns which player is winner.
-    /// if there are no winner, it retruns None.
-    pub fn winner(&self) -> Option<Player> {
-        match () {
-            _ if self.blk == Board::empty() => Some(Player::RED),
-            _ if self.red == Board::empty() => Some(Player::BLK),
-            _ => None,
+    /// make() applies the move in place and returns the record to unmake() it.
+    /// it avoids cloning the game, so searches can walk the tree without allocation.
+    pub fn make(&mut self, m: &Move) -> Undo {
+        let src = m.src.board();
+        let dst_pos = m.dst();
+        let dst = dst_pos.board();
+
+        let undo = Undo {
+            side: self.side,
+            jumping: self.jumping,
+            king: self.king,
+            passed: self.passed,
+            captured: if m.jump {
+                m.mid().board()
+            } else {
+                Board::empty()
+            },
+        };
+
+        let king = &mut self.king;
+        let (slf, opp) = match self.side {
+            Player::BLK => (&mut self.blk, &mut self.red),
+            Player::RED => (&mut self.red, &mut self.blk),
+        };
+
+        let is_king = (src & *king) != Board::empty();
+        let is_promotion = (dst & Self::PROMOTION_MASK) != Board::empty();
+
+        *slf = (*slf & !src) | dst;
+        *king &= !src;
+        if is_king || is_promotion {
+            *king |= dst;
         }
+
+        if m.jump {
+            *opp &= !undo.captured;
+            *king &= !undo.captured;
+            self.jumping = Some(dst_pos);
+        } else {
+            self.side = !self.side;
+            self.jumping = None;
+        }
+
+        // same as moves().next().is_none(), without enumerating moves.
+        let movable = match self.jumping {
+            Some(_) => (dst & self.jumpables()) != Board::empty(),
+            None => self.movables() != Board::empty() || self.jumpables() != Board::empty(),
+        };
+        self.passed = false;
+        if !movable {
+            self.side = !self.side;
+            self.jumping = None;
+            self.passed = self.blocked(m);
+        }
+
+        undo
     }
 
-    fn valid(&self, m: &Move) -> bool {
-        let king = m.src.is(self.king);
-        let dir_ok = m.dir.valid(self.side, king, m.src);
+    /// blocked() checks the move, after which the turn came back to the side to move, left
+    /// the opponent blocked. the turn also comes back when a jump sequence ends or the
+    /// opponent has no piece left, which is no pass.
+    fn blocked(&self, m: &Move) -> bool {
+        let opp = match self.side {
+            Player::BLK => self.red,
+            Player::RED => self.blk,
+        };
+        !m.jump && opp != Board::empty()
+    }
 
-        let board = m.src.board();
+    /// unmake() takes back the move which make() returned the undo record for.
+    pub fn unmake(&mut self, m: &Move, undo: &Undo) {
+        let src = m.src.board();
+        let dst = m.dst().board();
 
-        let gap = self.gap();
-        let op = if self.side == Player::BLK {
-            self.red
-        } else {
-            self.blk
+        let (slf, opp) = match undo.side {
+            Player::BLK => (&mut self.blk, &mut self.red),
+            Player::RED => (&mut self.red, &mut self.blk),
         };
 
-        let gap_ok = if m.jump {
-            let first = m.dir.apply(board);
-            let second = m.dir.apply(first);
+        *slf = (*slf & !dst) | src;
+        *opp |= undo.captured;
 
-            let has_op = (first & op) != Board::empty();
-            let has_gap = (second & gap) != Board::empty();
-            has_op && has_gap
-        } else {
-            let first = m.dir.apply(board);
-            (first & gap) != Board::empty()
+        self.king = undo.king;
+        self.side = undo.side;
+        self.jumping = undo.jumping;
+        self.passed = undo.passed;
+    }
+
+    /// key() returns the 64-bit Zobrist hash of the game, to look it up in tables.
+    /// different games may share a key, but only rarely.
+    pub fn key(&self) -> u64 {
+        Game::default().rekey(0, self)
+    }
+
+    /// rekey() returns the key of the next game from the key of this game. only the cells
+    /// which differ are hashed, so a search updates the key by each move instead of
+    /// hashing every game from scratch.
+    pub fn rekey(&self, key: u64, next: &Game) -> u64 {
+        let layers = |g: &Game| {
+            [
+                g.blk & !g.king,
+                g.blk & g.king,
+                g.red & !g.king,
+                g.red & g.king,
+                g.jumping.map_or(Board::empty(), Pos::board),
+            ]
         };
 
-        dir_ok && gap_ok
+        let mut key = key;
+        for (keys, (a, b)) in ZOBRIST
+            .iter()
+            .zip(layers(self).into_iter().zip(layers(next)))
+        {
+            for pos in (a ^ b).actives() {
+                key ^= keys[pos.index()];
+            }
+        }
+        if self.side != next.side {
+            key ^= ZOBRIST_SIDE;
+        }
+        if self.passed != next.passed {
+            key ^= ZOBRIST_PASSED;
+        }
+        key
+    }
+
+    /// flip() swaps the colors: the board is turned 180 degrees, and red and black pieces
+    /// and the side to move are swapped. the flipped game is the same game for the opponent.
+    pub fn flip(&self) -> Game {
+        Game {
+            side: !self.side,
+            jumping: self.jumping.map(Pos::rotate),
+            red: self.blk.rotate(),
+            blk: self.red.rotate(),
+            king: self.king.rotate(),
+            passed: self.passed,
+        }
+    }
+
+    /// canonical() returns the game seen from black's side, and whether it was flipped.
+    /// games which are the same up to colors share a canonical game, so it's suitable for
+    /// keys of opening books, endgame databases and evaluation caches.
+    pub fn canonical(&self) -> (Game, bool) {
+        match self.side {
+            Player::BLK => (self.clone(), false),
+            Player::RED => (self.flip(), true),
+        }
+    }
+
+    /// try_apply() validates the move against the rules before applying it.
+    /// it returns why the move is illegal instead of corrupting the game.
+    pub fn try_apply(&self, m: &Move) -> Result<Game, MoveError> {
+        if !m.src.on_board() {
+            return Err(MoveError::OffBoard);
+        }
+        if self.move_list().contains(m) {
+            return Ok(self.apply(m));
+        }
+        Err(self.illegal(m))
+    }
+
+    /// illegal() explains why the move on the board is not one of the candidates.
+    fn illegal(&self, m: &Move) -> MoveError {
+        let (slf, opp) = match self.side {
+            Player::BLK => (self.blk, self.red),
+            Player::RED => (self.red, self.blk),
+        };
+
+        match () {
+            _ if m.src.is(opp) => return MoveError::WrongSide,
+            _ if !m.src.is(slf) => return MoveError::NoPiece,
+            _ => (),
+        }
+
+        match self.jumping {
+            Some(pos) if pos != m.src => return MoveError::NotJumpingPiece,
+            Some(_) if !m.jump => return MoveError::CaptureRequired,
+            _ => (),
+        }
+
+        let dst = match m.jump {
+            true => m.dir.jump(m.src),
+            false => m.dir.step(m.src),
+        };
+        let (Some(first), Some(dst)) = (m.dir.step(m.src), dst) else {
+            return MoveError::OffBoard;
+        };
+
+        match () {
+            _ if !m.dir.valid_piece(self.side, m.src.is(self.king)) => MoveError::WrongDirection,
+            _ if !dst.is(self.gap()) => MoveError::Occupied,
+            _ if m.jump && !first.is(opp) => MoveError::NothingToCapture,
+            // every other move is legal, except a step while a capture is mandatory.
+            _ => MoveError::CaptureRequired,
+        }
+    }
+
+    /// winner() returns which player is winner.
+    /// if there are no winner, it retruns None.
+    pub fn winner(&self) -> Option<Player> {
+        self.winner_for(Goal::Normal)
+    }
+
+    /// winner_for() returns which player is winner when both players play for the goal.
+    /// if there are no winner, it returns None.
+    /// a blocked player has its turn passed by apply(), so it never loses the normal game by
+    /// the block, unlike Rules::winner().
+    pub fn winner_for(&self, goal: Goal) -> Option<Player> {
+        match goal {
+            Goal::Normal => match () {
+                _ if self.blk == Board::empty() => Some(Player::RED),
+                _ if self.red == Board::empty() => Some(Player::BLK),
+                _ => None,
+            },
+            Goal::Giveaway => match () {
+                _ if self.blk == Board::empty() => Some(Player::BLK),
+                _ if self.red == Board::empty() => Some(Player::RED),
+                // the opponent was blocked on its turn, which was passed by apply().
+                _ if self.passed => Some(!self.side),
+                // the other player may be unblocked by the next move, so only the side to
+                // move wins by being blocked.
+                _ if self.moves().next().is_none() => Some(self.side),
+                _ => None,
+            },
+        }
     }
 
     fn movables(&self) -> Board {
@@ -150,7 +429,9 @@ impl Game {
         movables
     }
 
-    fn jumpables(&self) -> Board {
+    /// jumpables() returns the pieces of the side to move which can jump.
+    /// captures are mandatory, so the game is in the middle of an exchange if it's not empty.
+    pub fn jumpables(&self) -> Board {
         match self.side {
             Player::BLK => self.blk_jumpables(),
             Player::RED => self.red_jumpables(),
@@ -205,42 +486,50 @@ impl Game {
 }
 
 #[cfg(test)]
-pub mod testutil {
+pub(crate) mod testutil {
     use super::*;
 
     use crate::player::Player;
     use crate::pos::Pos;
-    use unindent::unindent;
 
     pub fn game(side: Player, jumping: Option<Pos>, s: &str) -> Game {
-        let s = unindent(s);
-        let mut game = Game::default();
-        game.side = side;
-        game.jumping = jumping;
-
-        let lines = s.split("\n");
-        for (y, line) in lines.enumerate() {
-            for (x, c) in line.chars().enumerate() {
-                let pos = Pos::graphical(x as u8, y as u8).and_then(|pos| match c {
-                    'b' => Some((Player::BLK, false, pos)),
-                    'B' => Some((Player::BLK, true, pos)),
-                    'r' => Some((Player::RED, false, pos)),
-                    'R' => Some((Player::RED, true, pos)),
-                    _ => None,
-                });
-                match pos {
-                    Some((Player::BLK, _, pos)) => game.blk |= pos.board(),
-                    Some((Player::RED, _, pos)) => game.red |= pos.board(),
-                    _ => (),
-                };
-                match pos {
-                    Some((_, true, pos)) => game.king |= pos.board(),
-                    _ => (),
-                };
-            }
-        }
+        Game::from_diagram(side, jumping, s).unwrap()
+    }
+
+    /// step() finds the move from the square to the square on the game.
+    pub fn step(g: &Game, src: u8, dst: u8) -> Move {
+        g.moves()
+            .find(|m| m.src.square() == src && m.dst().square() == dst)
+            .expect("the move is legal")
+    }
 
-        game
+    /// tactical() returns a position with jumps, promotions and kings for both players.
+    pub fn tactical(side: Player) -> Game {
+        game(
+            side,
+            None,
+            r"
+                _._._._.
+                ._._._r_
+                _._b_._.
+                ._r_._._
+                _._._R_.
+                ._b_._b_
+                _B_._._.
+                ._._._._
+            ",
+        )
+    }
+
+    /// walk() visits every game reachable from the game in the depth, the game itself first.
+    pub fn walk(game: &Game, depth: u32, visit: &mut impl FnMut(&Game)) {
+        visit(game);
+        if depth == 0 {
+            return;
+        }
+        for m in game.moves() {
+            walk(&game.apply(&m), depth - 1, visit);
+        }
     }
 }
 
@@ -1071,11 +1360,198 @@ mod tests {
         }
     }
 
+    #[test]
+    fn try_apply_rejects_illegal_moves() {
+        let board = r"
+            _._._._.
+            ._._._._
+            _._._._.
+            ._._._._
+            _._r_._.
+            ._b_._r_
+            _b_._._.
+            b_._._._
+        ";
+        let mv = |x, y, dir, jump| Move {
+            src: Pos::new(x, y),
+            dir,
+            jump,
+        };
+
+        let cases = [
+            (
+                "Jump over the opponent piece",
+                None,
+                mv(2, 2, Dir::ForwardRight, true),
+                None,
+            ),
+            (
+                "Move the opponent piece",
+                None,
+                mv(2, 3, Dir::BackwardLeft, false),
+                Some(MoveError::WrongSide),
+            ),
+            (
+                "Move from an empty cell",
+                None,
+                mv(1, 1, Dir::ForwardLeft, false),
+                Some(MoveError::NoPiece),
+            ),
+            (
+                "Move out of the board",
+                None,
+                mv(3, 0, Dir::ForwardLeft, false),
+                Some(MoveError::OffBoard),
+            ),
+            (
+                "Move pone backward",
+                None,
+                mv(2, 2, Dir::BackwardLeft, false),
+                Some(MoveError::WrongDirection),
+            ),
+            (
+                "Move onto own piece",
+                None,
+                mv(3, 0, Dir::ForwardRight, false),
+                Some(MoveError::Occupied),
+            ),
+            (
+                "Jump over nothing",
+                None,
+                mv(2, 2, Dir::ForwardLeft, true),
+                Some(MoveError::NothingToCapture),
+            ),
+            (
+                "Move normally when a capture is possible",
+                None,
+                mv(2, 2, Dir::ForwardLeft, false),
+                Some(MoveError::CaptureRequired),
+            ),
+            (
+                "Move another piece while jumping",
+                Some(Pos::new(2, 2)),
+                mv(3, 1, Dir::ForwardLeft, false),
+                Some(MoveError::NotJumpingPiece),
+            ),
+            (
+                "Move from out of the board",
+                None,
+                Move {
+                    src: Pos::raw(40),
+                    dir: Dir::ForwardLeft,
+                    jump: false,
+                },
+                Some(MoveError::OffBoard),
+            ),
+        ];
+
+        for (msg, jumping, m, expected) in cases {
+            let game = testutil::game(Player::BLK, jumping, board);
+            let actual = game.try_apply(&m);
+
+            match expected {
+                Some(err) => assert_eq!(Err(err), actual, "{}", msg),
+                None => assert_eq!(Ok(game.apply(&m)), actual, "{}", msg),
+            }
+        }
+    }
+
+    #[test]
+    fn make_unmake_agrees_with_apply() {
+        let cases = [
+            ("Initial position", Game::initial()),
+            (
+                "Jumps, promotions and kings",
+                testutil::tactical(Player::BLK),
+            ),
+        ];
+
+        for (_, game) in cases {
+            testutil::walk(&game, 4, &mut |before| {
+                let mut game = before.clone();
+                for m in before.moves() {
+                    let undo = game.make(&m);
+                    assert_eq!(before.apply(&m), game, "make {:?} on {:?}", m, before);
+
+                    game.unmake(&m, &undo);
+                    assert_eq!(*before, game, "unmake {:?}", m);
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn move_generation_commutes_with_flip() {
+        let check = &mut |game: &Game| {
+            let flipped = game.flip();
+            assert_eq!(*game, flipped.flip(), "flip twice {:?}", game);
+
+            let mut expects: Vec<Move> = game.moves().map(|m| m.flip()).collect();
+            let mut actuals: Vec<Move> = flipped.moves().collect();
+            expects.sort();
+            actuals.sort();
+            assert_eq!(expects, actuals, "moves of {:?}", game);
+
+            for m in game.moves() {
+                let next = game.apply(&m);
+                assert_eq!(next.flip(), flipped.apply(&m.flip()), "apply {:?}", m);
+            }
+        };
+
+        testutil::walk(&Game::initial(), 4, check);
+        testutil::walk(&testutil::tactical(Player::RED), 4, check);
+    }
+
+    #[test]
+    fn rekey_agrees_with_key() {
+        testutil::walk(&testutil::tactical(Player::BLK), 3, &mut |game| {
+            let key = game.key();
+            for m in game.moves() {
+                let next = game.apply(&m);
+                assert_eq!(next.key(), game.rekey(key, &next), "{:?} on {:?}", m, game);
+                assert_ne!(key, next.key(), "{:?} on {:?}", m, game);
+            }
+        });
+    }
+
+    #[test]
+    fn move_list_holds_any_board() {
+        // 16 black kings on every other row, which no game reaches.
+        let kings = Board::ROWS[0] | Board::ROWS[2] | Board::ROWS[4] | Board::ROWS[6];
+        let game = Game {
+            side: Player::BLK,
+            jumping: None,
+            red: Board::empty(),
+            blk: kings,
+            king: kings,
+            passed: false,
+        };
+
+        let list = game.move_list();
+        assert_eq!(49, list.len());
+        assert_eq!(format!("{:?}", list.to_vec()), format!("{:?}", list));
+    }
+
+    #[test]
+    fn canonical_sees_from_black() {
+        let game = Game::initial().apply(&Move {
+            src: Pos::from_square(11).unwrap(),
+            dir: Dir::ForwardRight,
+            jump: false,
+        });
+
+        let (canonical, flipped) = game.canonical();
+
+        assert_eq!(true, flipped);
+        assert_eq!(Player::BLK, canonical.side);
+        assert_eq!((canonical.clone(), false), canonical.canonical());
+    }
+
     #[test]
     fn test_checkmate() {
         let cases = [
             (
-                "Game is cotninue normally",
+                "Game continues normally",
                 Player::RED,
                 r"
                     _._._._.
@@ -1128,4 +1604,118 @@ mod tests {
             assert_eq!(expected, actual, "{}", msg);
         }
     }
+
+    #[test]
+    fn test_giveaway_winner() {
+        let cases = [
+            (
+                "Game continues normally",
+                Player::RED,
+                r"
+                    _._._._.
+                    ._b_._._
+                    _._._._.
+                    ._._R_._
+                    _r_._._.
+                    ._b_._._
+                    _._._r_.
+                    ._._._._
+                ",
+                None,
+            ),
+            (
+                "Game only have black pieces",
+                Player::BLK,
+                r"
+                    _._._._.
+                    ._._._._
+                    _._._._.
+                    ._._B_._
+                    _b_._._.
+                    ._._._._
+                    _._._b_.
+                    ._._._._
+                ",
+                Some(Player::RED),
+            ),
+            (
+                "Game only have red pieces",
+                Player::RED,
+                r"
+                    _._._._.
+                    ._._._._
+                    _._._._.
+                    ._._R_._
+                    _r_._._.
+                    ._._._._
+                    _._._r_.
+                    ._._._._
+                ",
+                Some(Player::BLK),
+            ),
+            (
+                "Red is blocked",
+                Player::RED,
+                r"
+                    _._._._.
+                    ._._._._
+                    _._._._.
+                    ._._._._
+                    _._._._.
+                    r_._._._
+                    _b_._._.
+                    ._b_._._
+                ",
+                Some(Player::RED),
+            ),
+            (
+                "Red blocked itself, and black moves on without a pass",
+                Player::BLK,
+                r"
+                    _._._._.
+                    ._._._._
+                    _._._._.
+                    ._._._._
+                    _._._._.
+                    r_._._._
+                    _b_._._.
+                    ._b_._._
+                ",
+                None,
+            ),
+        ];
+
+        for (msg, player, game, expected) in cases {
+            let game = testutil::game(player, None, game);
+            let actual = game.winner_for(Goal::Giveaway);
+
+            assert_eq!(expected, actual, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn giveaway_is_won_by_the_passed_player() {
+        let board = |squares: &[u8]| {
+            squares.iter().fold(Board::empty(), |b, &n| {
+                b | Pos::from_square(n).unwrap().board()
+            })
+        };
+        let game = Game {
+            side: Player::BLK,
+            blk: board(&[5, 6]),
+            red: board(&[13]),
+            ..Default::default()
+        };
+
+        let m = testutil::step(&game, 5, 9);
+        let next = game.apply(&m);
+        let mut made = game.clone();
+        made.make(&m);
+
+        assert_eq!(Player::BLK, next.side, "Red is passed");
+        assert_eq!(next, made);
+        assert_eq!(Some(Player::RED), next.winner_for(Goal::Giveaway));
+        assert_eq!(None, next.winner_for(Goal::Normal));
+        assert_eq!(None, game.winner_for(Goal::Giveaway));
+    }
 }
@@ -0,0 +1,9 @@
+/// Goal is the outcome both players are playing for.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Goal {
+    /// Normal checkers: a player wins by capturing every opponent piece.
+    #[default]
+    Normal,
+    /// Giveaway checkers: a player wins by losing every own piece or by being blocked.
+    Giveaway,
+}
@@ -1,81 +1,289 @@
-use std::collections::HashSet;
+use std::collections::HashMap;
 
-use crate::game::Game;
+use crate::game::{Game, Undo};
+use crate::mv::Move;
 
-/// History is a sequence of game states.
-#[derive(Default)]
+/// History is a sequence of game states, stored as the start position and the moves from it.
+/// only the last game is kept as a whole; moves are taken back with their undo records.
+/// positions are counted by their hashes, and a hit is confirmed on the game restored by
+/// undoing moves from the last one.
+#[derive(Debug, Clone)]
 pub struct History {
-    seq: Vec<Game>,
-    set: HashSet<Game>,
+    start: Game,
+    last: Game,
+    moves: Vec<(Move, Undo)>,
+    hashes: Vec<u64>, // the hash of the game after each move, the start first.
+    counts: HashMap<u64, u32>, // how many games in hashes have each hash.
 }
 
 impl History {
-    pub fn push(&mut self, g: Game) {
-        self.seq.push(g.clone());
-        self.set.insert(g);
+    /// new() starts a history from the game.
+    pub fn new(start: Game) -> History {
+        let hash = start.key();
+        History {
+            last: start.clone(),
+            hashes: vec![hash],
+            counts: HashMap::from([(hash, 1)]),
+            start,
+            moves: vec![],
+        }
     }
 
-    pub fn last(&self) -> Option<&Game> {
-        self.seq.last()
+    /// start() returns the game the history starts from.
+    pub fn start(&self) -> &Game {
+        &self.start
     }
 
-    pub fn pop(&mut self) -> Option<Game> {
-        self.seq.pop().map(|g| { self.set.remove(&g); g })
+    /// last() returns the game after all moves.
+    pub fn last(&self) -> &Game {
+        &self.last
     }
 
+    /// len() returns the number of moves.
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    /// is_empty() checks no move is played yet.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// moves() iterates the played moves from the start.
+    pub fn moves(&self) -> impl Iterator<Item = Move> + '_ {
+        self.moves.iter().map(|(m, _)| *m)
+    }
+
+    /// push() plays the move on the last game.
+    pub fn push(&mut self, m: Move) {
+        let undo = self.last.make(&m);
+        let hash = self.last.key();
+        self.moves.push((m, undo));
+        self.hashes.push(hash);
+        *self.counts.entry(hash).or_insert(0) += 1;
+    }
+
+    /// pop() takes back the last move and returns it.
+    pub fn pop(&mut self) -> Option<Move> {
+        let (m, undo) = self.moves.pop()?;
+        self.last.unmake(&m, &undo);
+
+        let hash = self.hashes.pop().expect("a hash for each move");
+        match self.counts.get_mut(&hash) {
+            Some(n) if *n > 1 => *n -= 1,
+            _ => {
+                self.counts.remove(&hash);
+            }
+        }
+        Some(m)
+    }
+
+    /// truncate() takes back moves until `len` moves are left.
+    /// it does nothing if there are `len` or less moves.
+    pub fn truncate(&mut self, len: usize) {
+        while self.len() > len {
+            self.pop();
+        }
+    }
+
+    /// get() returns the game after `i` moves, so get(0) is the start and get(len()) is
+    /// the last game. it's restored by replaying moves.
+    pub fn get(&self, i: usize) -> Option<Game> {
+        if i > self.len() {
+            return None;
+        }
+        self.iter().nth(i)
+    }
+
+    /// iter() iterates the games from the start to the last one.
+    pub fn iter(&self) -> Games<'_> {
+        Games {
+            game: Some(self.start.clone()),
+            moves: self.moves.iter(),
+        }
+    }
+
+    /// contains() checks the game has appeared in this history.
     pub fn contains(&self, g: &Game) -> bool {
-        self.set.contains(g)
+        self.occurrences(g) > 0
+    }
+
+    /// occurrences() returns how many times the game has appeared in this history.
+    /// earlier games are restored only when the hash is counted, to tell a collision from
+    /// a repetition, and no further back than the earliest game with the hash.
+    pub fn occurrences(&self, g: &Game) -> u32 {
+        let hash = g.key();
+        let Some(&count) = self.counts.get(&hash) else {
+            return 0;
+        };
+
+        let mut game = self.last.clone();
+        let mut left = count;
+        let mut found = 0;
+        for i in (0..self.hashes.len()).rev() {
+            if self.hashes[i] == hash {
+                found += (game == *g) as u32;
+                left -= 1;
+                if left == 0 {
+                    break;
+                }
+            }
+            if i > 0 {
+                let (m, undo) = &self.moves[i - 1];
+                game.unmake(m, undo);
+            }
+        }
+        found
+    }
+
+    /// repetitions() returns how many times the last game has appeared, including itself.
+    /// it's known without restoring any game unless another game has the same hash.
+    pub fn repetitions(&self) -> u32 {
+        match self.counts.get(&self.hashes[self.len()]) {
+            Some(1) => 1,
+            _ => self.occurrences(&self.last),
+        }
+    }
+}
+
+impl Default for History {
+    fn default() -> Self {
+        History::new(Game::initial())
+    }
+}
+
+/// Games iterates the games of a History by replaying its moves.
+pub struct Games<'a> {
+    game: Option<Game>,
+    moves: std::slice::Iter<'a, (Move, Undo)>,
+}
+
+impl Iterator for Games<'_> {
+    type Item = Game;
+
+    fn next(&mut self) -> Option<Game> {
+        let game = self.game.take()?;
+        self.game = self.moves.next().map(|(m, _)| game.apply(m));
+        Some(game)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::board::Board;
-    use crate::game::Game;
+    use crate::game::testutil;
     use crate::player::Player;
+    use crate::pos::Pos;
     use pretty_assertions::assert_eq;
 
+    /// kings() returns a game where each player has a king in a corner.
+    fn kings() -> Game {
+        testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _B_._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._R_
+            ",
+        )
+    }
+
     #[test]
     fn push_pop_identity() {
-        let g = Game {
-            side: Player::BLK,
-            jumping: None,
-            red: Board::new(0b0000_0001_0000_0000_0001_0010_0000_0100),
-            blk: Board::new(0b0100_0000_0000_0010_0000_0000_0000_0010),
-            king: Board::new(0b0000_0001_0000_0000_0001_0000_0100_0000),
-        };
-        let expected = Some(g.clone());
+        let start = Game::initial();
+        let m = testutil::step(&start, 11, 15);
 
         let mut h = History::default();
-        h.push(g);
+        h.push(m);
+        let next = h.last().clone();
         let actual = h.pop();
 
-        assert_eq!(expected, actual);
+        assert_eq!(Some(m), actual);
+        assert_eq!(&start, h.last());
+        assert_eq!(true, h.contains(&start));
+        assert_eq!(false, h.contains(&next));
+        assert_eq!(None, h.pop());
     }
 
     #[test]
     fn pushed_contains_identity() {
-        let g1 = Game {
-            side: Player::BLK,
-            jumping: None,
-            red: Board::new(0b0000_0001_0000_0000_0001_0010_0000_0100),
-            blk: Board::new(0b0100_0000_0000_0010_0000_0000_0000_0010),
-            king: Board::new(0b0000_0001_0000_0000_0001_0000_0100_0000),
-        };
-        let g2 = Game {
-            side: Player::BLK,
-            jumping: None,
-            red: Board::new(0b0100_0000_0000_0010_0000_0000_0000_0010),
-            blk: Board::new(0b0000_0001_0000_0000_0001_0010_0000_0100),
-            king: Board::new(0b0000_0001_0000_0000_0001_0000_0100_0000),
-        };
+        let start = Game::initial();
+        let mut h = History::new(start.clone());
+        h.push(testutil::step(&start, 11, 15));
+        let next = h.last().clone();
+        h.push(testutil::step(&next, 22, 18));
 
-        let mut h = History::default();
-        h.push(g1.clone());
-        h.push(g2.clone());
+        assert_eq!(true, h.contains(&start));
+        assert_eq!(true, h.contains(&next));
+        assert_eq!(true, h.contains(h.last()));
+        assert_eq!(false, h.contains(&kings()));
+    }
+
+    #[test]
+    fn pop_keeps_earlier_occurrences() {
+        let start = kings();
+        let mut h = History::new(start.clone());
+        for (src, dst) in [(32, 28), (1, 5), (28, 32), (5, 1)] {
+            let m = testutil::step(h.last(), src, dst);
+            h.push(m);
+        }
+
+        assert_eq!(&start, h.last());
+        assert_eq!(2, h.occurrences(&start));
+        assert_eq!(2, h.repetitions());
+
+        h.pop();
+
+        assert_eq!(1, h.occurrences(&start));
+        assert_eq!(true, h.contains(&start));
+        assert_eq!(1, h.repetitions());
+    }
+
+    #[test]
+    fn hash_collision_is_not_a_repetition() {
+        let other = Game::initial();
+        let mut h = History::new(kings());
+        // pretend the start collides with another game.
+        h.hashes[0] = other.key();
+        h.counts = HashMap::from([(other.key(), 1)]);
+
+        assert_eq!(0, h.occurrences(&other));
+        assert_eq!(false, h.contains(&other));
+        assert_eq!(1, h.repetitions());
+    }
+
+    #[test]
+    fn get_iter_and_truncate() {
+        let start = kings();
+        let mut h = History::new(start.clone());
+        let mut expects = vec![start.clone()];
+        for (src, dst) in [(32, 28), (1, 5), (28, 24)] {
+            let m = testutil::step(h.last(), src, dst);
+            h.push(m);
+            expects.push(h.last().clone());
+        }
+
+        assert_eq!(3, h.len());
+        assert_eq!(expects, h.iter().collect::<Vec<_>>());
+        for (i, expect) in expects.iter().enumerate() {
+            assert_eq!(Some(expect), h.get(i).as_ref(), "get({})", i);
+        }
+        assert_eq!(None, h.get(4));
+
+        h.truncate(1);
 
-        assert_eq!(true, h.contains(&g1));
-        assert_eq!(true, h.contains(&g2));
+        assert_eq!(1, h.len());
+        assert_eq!(&expects[1], h.last());
+        assert_eq!(
+            vec![Pos::from_square(32).unwrap()],
+            h.moves().map(|m| m.src).collect::<Vec<_>>()
+        );
+        assert_eq!(false, h.contains(&expects[2]));
     }
 }
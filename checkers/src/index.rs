@@ -0,0 +1,319 @@
+use crate::board::Board;
+use crate::game::Game;
+use crate::player::Player;
+use crate::pos::Pos;
+
+/// Material is the signature of a position: the number of men and kings for each player.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Material {
+    pub blk_men: u32,
+    pub blk_kings: u32,
+    pub red_men: u32,
+    pub red_kings: u32,
+}
+
+/// BINOMIAL is the table of binomial coefficients, BINOMIAL[n][k] = nCk.
+const BINOMIAL: [[u64; 33]; 33] = {
+    let mut table = [[0; 33]; 33];
+    let mut n = 0;
+    while n < 33 {
+        table[n][0] = 1;
+        let mut k = 1;
+        while k <= n {
+            table[n][k] = table[n - 1][k - 1] + table[n - 1][k];
+            k += 1;
+        }
+        n += 1;
+    }
+    table
+};
+
+fn choose(n: u32, k: u32) -> u64 {
+    if n < k {
+        return 0;
+    }
+    BINOMIAL[n as usize][k as usize]
+}
+
+/// BLK_HOME is black's home row, where red men can't stand since they would be crowned.
+const BLK_HOME: Board = Board::RED_PROMOTION;
+/// BLK_FIELD is the cells other than black's home row where black men can stand.
+const BLK_FIELD: Board = Board::new(!(Board::BLK_PROMOTION.bits() | BLK_HOME.bits()));
+/// RED_MEN is the cells where red men can stand.
+const RED_MEN: Board = Board::new(!Board::RED_PROMOTION.bits());
+
+impl Material {
+    /// of() returns the material signature of the game.
+    pub fn of(g: &Game) -> Material {
+        Material {
+            blk_men: (g.blk & !g.king).count(),
+            blk_kings: (g.blk & g.king).count(),
+            red_men: (g.red & !g.king).count(),
+            red_kings: (g.red & g.king).count(),
+        }
+    }
+
+    /// count() returns the number of positions with this material, which is the size of the
+    /// index space of rank() and unrank().
+    pub fn count(&self) -> u64 {
+        (0..=self.blk_men.min(4)).map(|home| self.slice(home)).sum()
+    }
+
+    /// slice() counts positions which have `home` black men on black's home row.
+    /// red men can't stand on that row, so the cells left for them depend on it.
+    fn slice(&self, home: u32) -> u64 {
+        self.radices(home).map_or(0, |r| r.iter().product())
+    }
+
+    /// radices() returns the number of choices for each digit of the index in the slice:
+    /// black men on the home row and elsewhere, red men, black kings and red kings.
+    /// it returns None if the material doesn't fit in the board.
+    fn radices(&self, home: u32) -> Option<[u64; 5]> {
+        let Material {
+            blk_men: bm,
+            blk_kings: bk,
+            red_men: rm,
+            red_kings: rk,
+        } = *self;
+
+        let field = bm.checked_sub(home)?;
+        let red_men = (28 + home).checked_sub(bm)?;
+        let blk_kings = 32u32.checked_sub(bm)?.checked_sub(rm)?;
+        let red_kings = blk_kings.checked_sub(bk)?;
+        Some([
+            choose(4, home),
+            choose(24, field),
+            choose(red_men, rm),
+            choose(blk_kings, bk),
+            choose(red_kings, rk),
+        ])
+    }
+}
+
+impl Game {
+    /// rank() returns the perfect index of this position among the positions with the same
+    /// Material. the side to move is not a part of the index.
+    /// it returns None for positions which have no index: a jumping piece, or men on their
+    /// promotion row.
+    pub fn rank(&self) -> Option<u64> {
+        let men = !self.king;
+        let (bm, rm) = (self.blk & men, self.red & men);
+        let (bk, rk) = (self.blk & self.king, self.red & self.king);
+
+        let crowned = (bm & Board::BLK_PROMOTION) | (rm & Board::RED_PROMOTION);
+        if self.jumping.is_some() || !crowned.is_empty() || !(self.blk & self.red).is_empty() {
+            return None;
+        }
+
+        let material = Material::of(self);
+        let home = (bm & BLK_HOME).count();
+
+        let red_men = RED_MEN & !bm;
+        let blk_kings = !(bm | rm);
+        let red_kings = blk_kings & !bk;
+
+        let digits = [
+            (subset_rank(bm & BLK_HOME, BLK_HOME), choose(4, home)),
+            (
+                subset_rank(bm & BLK_FIELD, BLK_FIELD),
+                choose(24, bm.count() - home),
+            ),
+            (
+                subset_rank(rm, red_men),
+                choose(red_men.count(), rm.count()),
+            ),
+            (
+                subset_rank(bk, blk_kings),
+                choose(blk_kings.count(), bk.count()),
+            ),
+            (
+                subset_rank(rk, red_kings),
+                choose(red_kings.count(), rk.count()),
+            ),
+        ];
+        let index = digits.iter().fold(0, |acc, &(d, radix)| acc * radix + d);
+
+        let offset: u64 = (0..home).map(|h| material.slice(h)).sum();
+        Some(offset + index)
+    }
+
+    /// unrank() returns the position of the index among the positions with the Material.
+    /// it returns None if the index is out of the range of Material::count().
+    pub fn unrank(material: Material, side: Player, index: u64) -> Option<Game> {
+        let mut index = index;
+        let mut home = 0;
+        loop {
+            if material.blk_men.min(4) < home {
+                return None;
+            }
+            let size = material.slice(home);
+            if index < size {
+                break;
+            }
+            index -= size;
+            home += 1;
+        }
+
+        let Material {
+            blk_men,
+            blk_kings,
+            red_men,
+            red_kings,
+        } = material;
+
+        // digits are decoded from the least significant one, the reverse order of rank().
+        let mut digits = [0; 5];
+        let radices = material.radices(home)?;
+        for (digit, radix) in digits.iter_mut().zip(radices).rev() {
+            *digit = index % radix;
+            index /= radix;
+        }
+        let [home_digit, field_digit, rm_digit, bk_digit, rk_digit] = digits;
+
+        let bm = subset_unrank(home_digit, home, BLK_HOME)
+            | subset_unrank(field_digit, blk_men - home, BLK_FIELD);
+        let rm = subset_unrank(rm_digit, red_men, RED_MEN & !bm);
+        let bk = subset_unrank(bk_digit, blk_kings, !(bm | rm));
+        let rk = subset_unrank(rk_digit, red_kings, !(bm | rm | bk));
+
+        Some(Game {
+            side,
+            jumping: None,
+            blk: bm | bk,
+            red: rm | rk,
+            king: bk | rk,
+            passed: false,
+        })
+    }
+}
+
+/// subset_rank() returns the colexicographic rank of the subset among the same sized subsets
+/// of the domain.
+fn subset_rank(subset: Board, domain: Board) -> u64 {
+    subset
+        .actives()
+        .enumerate()
+        .map(|(i, pos)| {
+            let below = domain & Board::new(pos.board().bits() - 1);
+            choose(below.count(), i as u32 + 1)
+        })
+        .sum()
+}
+
+/// subset_unrank() returns the subset of the domain with `k` cells, which has the rank.
+fn subset_unrank(rank: u64, k: u32, domain: Board) -> Board {
+    let cells: Vec<Pos> = domain.actives().collect();
+
+    let mut rank = rank;
+    let mut n = cells.len() as u32;
+    let mut subset = Board::empty();
+    for i in (1..=k).rev() {
+        n -= 1;
+        while rank < choose(n, i) {
+            n -= 1;
+        }
+        rank -= choose(n, i);
+        subset |= cells[n as usize].board();
+    }
+    subset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    /// subsets() enumerates every subset of the domain with k cells by brute force.
+    fn subsets(domain: Board, k: u32) -> Vec<Board> {
+        if k == 0 {
+            return vec![Board::empty()];
+        }
+        domain
+            .actives()
+            .flat_map(|pos| {
+                let rest = domain & !Board::new((pos.board().bits() << 1).wrapping_sub(1));
+                subsets(rest, k - 1)
+                    .into_iter()
+                    .map(move |b| b | pos.board())
+            })
+            .collect()
+    }
+
+    /// positions() counts every position with the material by brute force.
+    fn positions(m: Material) -> u64 {
+        let mut count = 0;
+        for bm in subsets(Board::FULL & !Board::BLK_PROMOTION, m.blk_men) {
+            for rm in subsets(RED_MEN & !bm, m.red_men) {
+                for bk in subsets(!(bm | rm), m.blk_kings) {
+                    count += subsets(!(bm | rm | bk), m.red_kings).len() as u64;
+                }
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn rank_and_unrank_round_trip() {
+        let cases = [
+            (1, 0, 0, 0),
+            (0, 1, 0, 1),
+            (1, 0, 1, 0),
+            (2, 0, 1, 0),
+            (1, 1, 1, 0),
+            (0, 2, 1, 0),
+            (1, 0, 1, 1),
+            (3, 0, 0, 1),
+            (4, 0, 0, 0),
+            (2, 0, 2, 0),
+        ];
+
+        for (blk_men, blk_kings, red_men, red_kings) in cases {
+            let material = Material {
+                blk_men,
+                blk_kings,
+                red_men,
+                red_kings,
+            };
+            let count = material.count();
+            assert_eq!(positions(material), count, "count of {:?}", material);
+
+            for index in 0..count {
+                let game = Game::unrank(material, Player::BLK, index).unwrap();
+                assert_eq!(Ok(()), game.validate(), "{:?} at {}", material, index);
+                assert_eq!(material, Material::of(&game), "{:?} at {}", material, index);
+                assert_eq!(Some(index), game.rank(), "{:?} at {}", material, index);
+            }
+            assert_eq!(None, Game::unrank(material, Player::BLK, count));
+        }
+    }
+
+    #[test]
+    fn impossible_material_has_no_positions() {
+        let cases = [
+            ("Too many men", 20, 0, 20, 0),
+            ("Too many kings", 0, 30, 0, 30),
+            ("Too many black men", 29, 0, 0, 0),
+        ];
+
+        for (msg, blk_men, blk_kings, red_men, red_kings) in cases {
+            let material = Material {
+                blk_men,
+                blk_kings,
+                red_men,
+                red_kings,
+            };
+            assert_eq!(0, material.count(), "{}", msg);
+            assert_eq!(None, Game::unrank(material, Player::BLK, 0), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn rank_initial_position() {
+        let game = Game::initial();
+        let material = Material::of(&game);
+
+        let index = game.rank().unwrap();
+
+        assert_eq!(Some(game), Game::unrank(material, Player::BLK, index));
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::board::Board;
+use crate::game::Game;
+use crate::player::Player;
+use crate::pos::Pos;
+
+/// Violation is a broken invariant of a Game, which makes the position impossible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// cells occupied by both red and black pieces.
+    Overlap(Board),
+    /// king bits on cells without any piece.
+    KingOnEmpty(Board),
+    /// pones left on their own promotion row without being crowned.
+    UncrownedPone(Board),
+    /// a player has more pieces than the 12 it starts with.
+    TooManyPieces(Player, u32),
+    /// the jumping cell doesn't hold a piece of the side to move.
+    JumpingWithoutPiece(Pos),
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            Self::Overlap(b) => write!(f, "red and black pieces overlap at {:?}", b),
+            Self::KingOnEmpty(b) => write!(f, "kings are marked on empty cells at {:?}", b),
+            Self::UncrownedPone(b) => write!(f, "pones are not crowned at {:?}", b),
+            Self::TooManyPieces(p, n) => write!(f, "{:?} has {} pieces", p, n),
+            Self::JumpingWithoutPiece(pos) => write!(f, "no piece is jumping at {}", pos),
+        }
+    }
+}
+
+impl Game {
+    /// MAX_PIECES is the number of pieces each player starts with.
+    pub const MAX_PIECES: u32 = 12;
+
+    /// violations() returns every broken invariant of this game.
+    /// it is empty if the position is possible.
+    pub fn violations(&self) -> Vec<Violation> {
+        let mut v = vec![];
+
+        let overlap = self.red & self.blk;
+        if !overlap.is_empty() {
+            v.push(Violation::Overlap(overlap));
+        }
+
+        let kings = self.king & !(self.red | self.blk);
+        if !kings.is_empty() {
+            v.push(Violation::KingOnEmpty(kings));
+        }
+
+        let pones =
+            !self.king & ((self.blk & Board::BLK_PROMOTION) | (self.red & Board::RED_PROMOTION));
+        if !pones.is_empty() {
+            v.push(Violation::UncrownedPone(pones));
+        }
+
+        for (p, b) in [(Player::BLK, self.blk), (Player::RED, self.red)] {
+            if Self::MAX_PIECES < b.count() {
+                v.push(Violation::TooManyPieces(p, b.count()));
+            }
+        }
+
+        let slf = match self.side {
+            Player::BLK => self.blk,
+            Player::RED => self.red,
+        };
+        match self.jumping {
+            Some(pos) if !pos.is(slf) => v.push(Violation::JumpingWithoutPiece(pos)),
+            _ => (),
+        }
+
+        v
+    }
+
+    /// validate() checks every invariant of this game.
+    pub fn validate(&self) -> std::result::Result<(), Vec<Violation>> {
+        let v = self.violations();
+        if v.is_empty() {
+            Ok(())
+        } else {
+            Err(v)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn initial_position_is_valid() {
+        assert_eq!(Ok(()), Game::initial().validate());
+    }
+
+    #[test]
+    fn violations_report_every_broken_invariant() {
+        let game = Game {
+            side: Player::RED,
+            jumping: Some(Pos::raw(9)),
+            red: Board::new(0b1111_1111_1111_0000_0000_0001_0000_0011),
+            blk: Board::new(0b1000_0000_0000_0000_0000_1111_1111_1111),
+            king: Board::new(0b0000_0000_0000_1000_0000_0000_0000_0001),
+            passed: false,
+        };
+
+        let expect = vec![
+            Violation::Overlap(Board::new(0b1000_0000_0000_0000_0000_0001_0000_0011)),
+            Violation::KingOnEmpty(Board::new(0b0000_0000_0000_1000_0000_0000_0000_0000)),
+            Violation::UncrownedPone(Board::new(0b1000_0000_0000_0000_0000_0000_0000_0010)),
+            Violation::TooManyPieces(Player::BLK, 13),
+            Violation::TooManyPieces(Player::RED, 15),
+            Violation::JumpingWithoutPiece(Pos::raw(9)),
+        ];
+
+        assert_eq!(expect, game.violations());
+    }
+}
@@ -1,17 +1,52 @@
 pub mod board;
+pub mod diagram;
 pub mod dir;
+pub mod error;
 pub mod game;
+pub mod goal;
 pub mod history;
+pub mod index;
+pub mod invariant;
 pub mod mv;
+pub mod ordering;
 pub mod piece;
 pub mod player;
+pub mod ponder;
 pub mod pos;
+pub mod record;
+pub mod retro;
+mod rng;
+pub mod rules;
+pub mod search;
+pub mod strength;
+#[cfg(any(test, feature = "suite"))]
+pub mod suite;
+pub mod table;
+pub mod time;
+pub mod tree;
+pub mod turkish;
+pub mod turn;
 
 pub use crate::board::*;
+pub use crate::diagram::*;
 pub use crate::dir::*;
+pub use crate::error::*;
 pub use crate::game::*;
+pub use crate::goal::*;
 pub use crate::history::*;
+pub use crate::index::*;
+pub use crate::invariant::*;
 pub use crate::mv::*;
+pub use crate::ordering::*;
 pub use crate::piece::*;
 pub use crate::player::*;
+pub use crate::ponder::*;
 pub use crate::pos::*;
+pub use crate::record::*;
+pub use crate::rules::*;
+pub use crate::search::*;
+pub use crate::strength::*;
+pub use crate::table::*;
+pub use crate::time::*;
+pub use crate::tree::*;
+pub use crate::turn::*;
@@ -1,7 +1,10 @@
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Deref, DerefMut};
+
 use crate::dir::Dir;
 use crate::pos::Pos;
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Move {
     pub src: Pos,
     pub dir: Dir,
@@ -35,20 +38,118 @@ impl Move {
         ]
     }
 
+    /// flip() returns the same move on the color flipped game, see Game::flip().
+    pub fn flip(&self) -> Move {
+        Move {
+            src: self.src.rotate(),
+            dir: self.dir.reverse(),
+            jump: self.jump,
+        }
+    }
+
     /// dst() return destination position of this move.
     pub fn dst(&self) -> Pos {
-        let mut moved = self.dir.apply(self.src.board());
-        if self.jump {
-            moved = self.dir.apply(moved);
-        }
+        let dst = match self.jump {
+            true => self.dir.jump(self.src),
+            false => self.dir.step(self.src),
+        };
         // it must has a position if this move is valid.
-        let v: Vec<Pos> = moved.actives().collect();
-        v[0]
+        dst.expect("the move leaves the board")
     }
 
     /// mid() returns middle position(=the cell contains a peace captured by jump) of jump move.
     pub fn mid(&self) -> Pos {
-        let v: Vec<Pos> = self.dir.apply(self.src.board()).actives().collect();
-        v[0]
+        self.dir.step(self.src).expect("the move leaves the board")
+    }
+}
+
+impl Display for Move {
+    /// fmt() writes the move in standard notation, like "11-15" or "15x24".
+    /// a move leaving the board is written with "?" for its destination, like "4-?".
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let sep = if self.jump { 'x' } else { '-' };
+        let dst = match self.jump {
+            true => self.dir.jump(self.src),
+            false => self.dir.step(self.src),
+        };
+        match dst {
+            Some(dst) => write!(f, "{}{}{}", self.src, sep, dst),
+            None => write!(f, "{}{}?", self.src, sep),
+        }
+    }
+}
+
+/// MoveList is a fixed-capacity list of moves, which lives on the stack.
+#[derive(Clone, Copy)]
+pub struct MoveList {
+    moves: [Move; MoveList::CAPACITY],
+    len: usize,
+}
+
+impl MoveList {
+    /// CAPACITY is enough for any board, even one which isn't reachable from the initial
+    /// position: every cell has 4 directions at most.
+    pub const CAPACITY: usize = 4 * 32;
+
+    const NONE: Move = Move {
+        src: Pos::raw(0),
+        dir: Dir::ForwardRight,
+        jump: false,
+    };
+
+    pub const fn new() -> MoveList {
+        MoveList {
+            moves: [Self::NONE; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    pub fn push(&mut self, m: Move) {
+        self.moves[self.len] = m;
+        self.len += 1;
+    }
+}
+
+impl fmt::Debug for MoveList {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl IntoIterator for MoveList {
+    type Item = Move;
+    type IntoIter = std::iter::Take<std::array::IntoIter<Move, { MoveList::CAPACITY }>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.moves.into_iter().take(self.len)
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
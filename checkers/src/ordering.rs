@@ -0,0 +1,132 @@
+use crate::board::Board;
+use crate::game::Game;
+use crate::mv::{Move, MoveList};
+
+/// MoveOrdering learns which moves cause cutoffs, so the search can try them first.
+/// moves are tried in this order: the hash move, promotions, killer moves, and the others
+/// by the history heuristic.
+#[derive(Debug, Clone)]
+pub struct MoveOrdering {
+    killers: Vec<[Option<Move>; 2]>, // quiet moves which caused a cutoff, for each ply.
+    history: [[u32; 4]; 32],         // how good quiet moves were, by the source and direction.
+}
+
+impl MoveOrdering {
+    const HASH: u32 = u32::MAX;
+    const PROMOTION: u32 = 1 << 30;
+    const KILLERS: [u32; 2] = [1 << 29, 1 << 28];
+    /// HISTORY_MAX keeps the history below the killer moves.
+    const HISTORY_MAX: u32 = 1 << 27;
+
+    pub fn new() -> MoveOrdering {
+        MoveOrdering {
+            killers: vec![],
+            history: [[0; 4]; 32],
+        }
+    }
+
+    /// clear() forgets everything learned.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// order() sorts the moves of the game at the ply, the most promising first.
+    pub fn order(&self, g: &Game, moves: &mut MoveList, hash: Option<Move>, ply: u32) {
+        let killers = self.killers.get(ply as usize).copied().unwrap_or_default();
+        moves.sort_unstable_by_key(|m| {
+            let priority = match () {
+                _ if Some(*m) == hash => Self::HASH,
+                _ if promotes(g, m) => Self::PROMOTION,
+                _ if Some(*m) == killers[0] => Self::KILLERS[0],
+                _ if Some(*m) == killers[1] => Self::KILLERS[1],
+                _ => self.history[m.src.index()][m.dir as usize],
+            };
+            std::cmp::Reverse(priority)
+        });
+    }
+
+    /// cutoff() learns the move caused a cutoff at the ply with the remaining depth.
+    /// jumps are forced, so only quiet moves are learned.
+    pub fn cutoff(&mut self, m: Move, ply: u32, depth: u32) {
+        if m.jump {
+            return;
+        }
+
+        let ply = ply as usize;
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None; 2]);
+        }
+        let killers = &mut self.killers[ply];
+        if killers[0] != Some(m) {
+            killers[1] = killers[0];
+            killers[0] = Some(m);
+        }
+
+        let value = &mut self.history[m.src.index()][m.dir as usize];
+        *value = value.saturating_add(depth * depth);
+        if *value >= Self::HISTORY_MAX {
+            for value in self.history.iter_mut().flatten() {
+                *value /= 2;
+            }
+        }
+    }
+}
+
+/// promotes() checks the move crowns a pone.
+pub(crate) fn promotes(g: &Game, m: &Move) -> bool {
+    !m.src.is(g.king) && m.dst().is(Board::PROMOTION)
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::testutil;
+    use crate::player::Player;
+    use crate::pos::Pos;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn order_by_priority() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                b_._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._b_._.
+                ._B_._._
+            ",
+        );
+        let find = |src: (u8, u8), dst: (u8, u8)| {
+            game.moves()
+                .find(|m| {
+                    Some(m.src) == Pos::graphical(src.0, src.1)
+                        && Some(m.dst()) == Pos::graphical(dst.0, dst.1)
+                })
+                .unwrap()
+        };
+        let hash = find((2, 7), (1, 6));
+        let promotion = find((0, 1), (1, 0));
+        let killer = find((3, 6), (4, 5));
+        let good = find((3, 6), (2, 5));
+
+        let mut ordering = MoveOrdering::new();
+        ordering.cutoff(good, 5, 1);
+        ordering.cutoff(killer, 3, 1);
+
+        let mut moves = game.move_list();
+        ordering.order(&game, &mut moves, Some(hash), 3);
+
+        assert_eq!(&[hash, promotion, killer, good], &moves[..4]);
+    }
+}
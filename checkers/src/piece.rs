@@ -1,6 +1,6 @@
 use derive_more::Not;
 
-#[derive(Debug, PartialEq, Eq, Not)]
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq, Not)]
 pub struct Piece(bool);
 
 pub const PONE: Piece = Piece(false);
@@ -0,0 +1,178 @@
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::game::Game;
+use crate::search::{SearchResult, Searcher, StopHandle};
+use crate::time::Clock;
+
+/// PonderHit passes the clock of a ponder hit to the pondering search.
+#[derive(Debug, Default)]
+pub(crate) struct PonderHit(Mutex<Option<Option<Clock>>>);
+
+impl PonderHit {
+    fn hit(&self, clock: Option<Clock>) {
+        *self.0.lock().expect("the ponder hit is never poisoned") = Some(clock);
+    }
+
+    /// take() returns the clock once the hit has come, None while pondering.
+    pub(crate) fn take(&self) -> Option<Option<Clock>> {
+        self.0
+            .lock()
+            .expect("the ponder hit is never poisoned")
+            .take()
+    }
+}
+
+/// Pondering is a search of the game expected after the opponent's reply, running in the
+/// background on the opponent's time. it searches without a clock until the opponent moves.
+#[derive(Debug)]
+pub struct Pondering {
+    game: Game,
+    hit: Arc<PonderHit>,
+    stop: StopHandle,
+    thread: JoinHandle<(Searcher, SearchResult)>,
+}
+
+impl Searcher {
+    /// ponder() starts searching the game in the background. usually the game is the one
+    /// after the best move and the expected reply of the last search. when the best move
+    /// starts a multi-jump, the rest of the jump is played before the reply:
+    /// ```
+    /// use checkers::search::{Options, Searcher};
+    /// use checkers::Game;
+    /// let game = Game::initial();
+    /// let mut searcher = Searcher::new(Options { depth: 4, ..Options::default() });
+    /// let result = searcher.search(&game);
+    /// let expected = game.apply(&result.best.unwrap()).apply(&result.reply.unwrap());
+    ///
+    /// let pondering = searcher.ponder(&expected);
+    /// // the opponent played the expected reply.
+    /// let (_searcher, result) = pondering.hit(None);
+    /// assert!(result.best.is_some());
+    /// ```
+    pub fn ponder(mut self, g: &Game) -> Pondering {
+        let hit = Arc::new(PonderHit::default());
+        self.set_hit(Some(hit.clone()));
+        let stop = self.stop_handle();
+
+        let game = g.clone();
+        let thread = thread::spawn(move || {
+            let result = self.search(&game);
+            self.set_hit(None);
+            (self, result)
+        });
+        Pondering {
+            game: g.clone(),
+            hit,
+            stop,
+            thread,
+        }
+    }
+}
+
+impl Pondering {
+    /// game() returns the game being pondered.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// hit() tells the opponent played into the game. the search goes on as the real search,
+    /// keeping what it has learned, with the clock started now. it returns the searcher and
+    /// the result once the search is over.
+    pub fn hit(self, clock: Option<Clock>) -> (Searcher, SearchResult) {
+        self.hit.hit(clock);
+        self.thread.join().expect("the pondering thread panicked")
+    }
+
+    /// miss() stops pondering because the opponent played another move, and returns the
+    /// searcher to search the real game.
+    pub fn miss(self) -> Searcher {
+        self.stop.stop();
+        self.thread.join().expect("the pondering thread panicked").0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::Options;
+    use pretty_assertions::assert_eq;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    /// expected() returns the game after the first moves of each side.
+    fn expected() -> Game {
+        let game = Game::initial();
+        let game = game.apply(&game.moves().next().unwrap());
+        game.apply(&game.moves().next().unwrap())
+    }
+
+    #[test]
+    fn ponder_hit_finishes_the_search() {
+        let options = Options {
+            depth: 6,
+            ..Options::default()
+        };
+        let expect = Searcher::new(options).search(&expected());
+
+        let pondering = Searcher::new(options).ponder(&expected());
+        assert_eq!(&expected(), pondering.game());
+        let (mut searcher, actual) = pondering.hit(None);
+
+        assert_eq!(expect, actual);
+        assert_eq!(expect, searcher.search(&expected()));
+    }
+
+    #[test]
+    fn ponder_hit_starts_the_clock() {
+        // the clock has no time left, so it stops the search as soon as it's started.
+        let clock = Clock {
+            remaining: Duration::ZERO,
+            increment: Duration::ZERO,
+            moves_to_go: None,
+        };
+        let (tx, rx) = mpsc::channel();
+        let mut searcher = Searcher::new(Options {
+            depth: 64,
+            clock: Some(clock),
+            ..Options::default()
+        });
+        searcher.on_progress(move |p| tx.send(p.depth).unwrap());
+
+        let pondering = searcher.ponder(&expected());
+        // pondering deepens without the clock until the hit.
+        let depths: Vec<u32> = (0..3).map(|_| depth(&rx)).collect();
+        let (_, result) = pondering.hit(Some(clock));
+
+        assert_eq!(vec![1, 2, 3], depths);
+        assert!(result.best.is_some());
+    }
+
+    #[test]
+    fn ponder_miss_stops_the_search() {
+        let (tx, rx) = mpsc::channel();
+        let mut searcher = Searcher::new(Options {
+            depth: 64,
+            ..Options::default()
+        });
+        searcher.on_progress(move |p| tx.send(p.depth).unwrap());
+
+        let pondering = searcher.ponder(&expected());
+        assert_eq!(1, depth(&rx));
+        // the search of depth 64 returns the searcher only when it's stopped.
+        let mut searcher = pondering.miss();
+        searcher.set_clock(Some(Clock {
+            remaining: Duration::ZERO,
+            increment: Duration::ZERO,
+            moves_to_go: None,
+        }));
+
+        assert!(searcher.search(&Game::initial()).best.is_some());
+    }
+
+    /// depth() waits for the depth of the next iteration the search reports.
+    fn depth(rx: &mpsc::Receiver<u32>) -> u32 {
+        rx.recv_timeout(Duration::from_secs(60))
+            .expect("the search deepens")
+    }
+}
@@ -1,16 +1,16 @@
 use crate::board::Board;
-use std::fmt::{Debug, Formatter, Result};
+use std::fmt::{Debug, Display, Formatter, Result};
 
 #[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Pos(u8);
 
 impl Pos {
-    pub fn raw(v: u8) -> Pos {
+    pub const fn raw(v: u8) -> Pos {
         Pos(v)
     }
 
     /// new() creates new Pos instance by internal position expression.
-    pub fn new(x: u8, y: u8) -> Pos {
+    pub const fn new(x: u8, y: u8) -> Pos {
         debug_assert!(x < 4);
         debug_assert!(y < 8);
 
@@ -20,7 +20,7 @@ impl Pos {
     /// graphical() converts graphical position into internal Pos if the position.
     /// if such position does not exists in internal expression, it will return None.
     pub fn graphical(x: u8, y: u8) -> Option<Pos> {
-        if (x + y) % 2 == 0 {
+        if (x + y).is_multiple_of(2) {
             // it means, unused cell so there are no internal expression.
             return None;
         }
@@ -31,15 +31,40 @@ impl Pos {
     }
 
     /// x returns internal position-x for this Pos.
-    pub fn x(self) -> u8 {
+    pub const fn x(self) -> u8 {
         self.0 & 0x3
     }
 
     /// y returns internal position-x for this Pos.
-    pub fn y(self) -> u8 {
+    pub const fn y(self) -> u8 {
         self.0 >> 2 // & 0x3 // We can assume self.0 is less than 0x1F for pre-condition.
     }
 
+    /// on_board checks this Pos is one of the 32 cells, as raw() accepts any value.
+    pub const fn on_board(self) -> bool {
+        self.0 < 32
+    }
+
+    /// index returns the bit index of this Pos, which is suitable for lookup tables.
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+
+    /// square returns the standard notation number of this Pos, from 1 to 32.
+    /// black's home rows are numbered 1 to 12, same as the standard board.
+    pub fn square(self) -> u8 {
+        self.0 + 1
+    }
+
+    /// from_square() converts standard notation number into Pos.
+    /// if the number is not from 1 to 32, it will return None.
+    pub fn from_square(n: u8) -> Option<Pos> {
+        match n {
+            1..=32 => Some(Pos(n - 1)),
+            _ => None,
+        }
+    }
+
     /// gx returns graphical position-x for this Pos.
     pub fn gx(self) -> u8 {
         7 - ((self.x() << 1) + (1 - (self.y() % 2)))
@@ -50,6 +75,11 @@ impl Pos {
         7 - self.y()
     }
 
+    /// rotate returns the position on the board turned 180 degrees.
+    pub const fn rotate(self) -> Pos {
+        Pos(31 - self.0)
+    }
+
     /// board returns bitboard's bit for this position.
     pub fn board(self) -> Board {
         Board::new(1 << self.0)
@@ -70,6 +100,12 @@ impl Pos {
     }
 }
 
+impl Display for Pos {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.square())
+    }
+}
+
 impl Debug for Pos {
     fn fmt(&self, f: &mut Formatter) -> Result {
         f.debug_tuple("Pos")
@@ -104,7 +140,7 @@ mod tests {
         ];
 
         for ((x, y), exp, msg) in cases {
-            let expect = exp.map(|v| Pos(v));
+            let expect = exp.map(Pos);
             let actual = Pos::graphical(x, y);
             assert_eq!(expect, actual, "{}", msg);
         }
@@ -0,0 +1,136 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::board::Board;
+use crate::game::Game;
+use crate::mv::Move;
+use crate::piece::{Piece, KING, PONE};
+use crate::player::Player;
+use crate::pos::Pos;
+
+/// Record describes a move fully: what moved where, what was captured and whether it crowned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Record {
+    pub side: Player,                   // which side made the move.
+    pub mv: Move,                       // the move itself.
+    pub piece: Piece,                   // the moved piece before the move.
+    pub dst: Pos,                       // the destination of the moved piece.
+    pub captured: Option<(Pos, Piece)>, // the captured piece, if the move is a jump.
+    pub promotion: bool,                // the moved pone is crowned by the move.
+}
+
+impl Game {
+    /// record() describes the move on this game, without applying it.
+    pub fn record(&self, m: &Move) -> Record {
+        let kind = |pos: Pos| if pos.is(self.king) { KING } else { PONE };
+
+        let piece = kind(m.src);
+        let dst = m.dst();
+        let promotion = piece == PONE && (dst.board() & Self::PROMOTION_MASK) != Board::empty();
+
+        Record {
+            side: self.side,
+            mv: *m,
+            piece,
+            dst,
+            captured: m.jump.then(|| (m.mid(), kind(m.mid()))),
+            promotion,
+        }
+    }
+}
+
+impl Display for Record {
+    /// fmt() writes the move in standard notation, like "11-15" or "15x24".
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.mv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dir::Dir;
+    use crate::game::testutil;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn record_describes_jump_with_promotion() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._R_._
+                _._._b_.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+            ",
+        );
+        let m = Move {
+            src: Pos::graphical(5, 2).unwrap(),
+            dir: Dir::ForwardLeft,
+            jump: true,
+        };
+
+        let expect = Record {
+            side: Player::BLK,
+            mv: m,
+            piece: PONE,
+            dst: Pos::graphical(3, 0).unwrap(),
+            captured: Some((Pos::graphical(4, 1).unwrap(), KING)),
+            promotion: true,
+        };
+        let actual = game.record(&m);
+
+        assert_eq!(expect, actual);
+        assert_eq!("22x31", actual.to_string());
+    }
+
+    #[test]
+    fn display_in_standard_notation() {
+        let cases = [
+            (
+                "Opening move",
+                Move {
+                    src: Pos::from_square(11).unwrap(),
+                    dir: Dir::ForwardRight,
+                    jump: false,
+                },
+                "11-15",
+            ),
+            (
+                "Red jump",
+                Move {
+                    src: Pos::from_square(24).unwrap(),
+                    dir: Dir::BackwardRight,
+                    jump: true,
+                },
+                "24x15",
+            ),
+            (
+                "Move leaving the board",
+                Move {
+                    src: Pos::new(3, 0),
+                    dir: Dir::ForwardLeft,
+                    jump: false,
+                },
+                "4-?",
+            ),
+            (
+                "Jump leaving the board",
+                Move {
+                    src: Pos::from_square(5).unwrap(),
+                    dir: Dir::ForwardRight,
+                    jump: true,
+                },
+                "5x?",
+            ),
+        ];
+
+        for (msg, m, expect) in cases {
+            assert_eq!(expect, m.to_string(), "{}", msg);
+        }
+    }
+}
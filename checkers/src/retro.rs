@@ -0,0 +1,272 @@
+use crate::board::Board;
+use crate::dir::Dir;
+use crate::game::Game;
+use crate::mv::Move;
+use crate::player::Player;
+use crate::pos::Pos;
+
+impl Game {
+    /// predecessors() enumerates every legal position which leads to this game by one move,
+    /// with the move. it's the inverse of moves() and apply(): a pair (p, m) is returned
+    /// if and only if p.moves() has m and p.apply(&m) is this game.
+    /// moves are taken back with uncapturing any kind of piece and uncrowning kings on the
+    /// promotion row.
+    pub fn predecessors(&self) -> Vec<(Game, Move)> {
+        let mut preds = vec![];
+
+        for side in self.movers() {
+            let slf = match side {
+                Player::BLK => self.blk,
+                Player::RED => self.red,
+            };
+            let dsts = match self.jumping {
+                Some(pos) => pos.board() & slf,
+                None => slf,
+            };
+
+            for dst in dsts.actives() {
+                for dir in Dir::ALL {
+                    for jump in [false, true] {
+                        self.unmove(side, dst, dir, jump, &mut preds);
+                    }
+                }
+            }
+        }
+
+        preds
+    }
+
+    /// movers() returns the sides which could have made the last move.
+    /// the last mover keeps the turn while jumping, or when its opponent was passed.
+    fn movers(&self) -> Vec<Player> {
+        match self.jumping {
+            Some(_) => vec![self.side],
+            None => vec![!self.side, self.side],
+        }
+    }
+
+    /// unmove() takes back the move of the side to the dst in every possible way and
+    /// pushes the legal ones.
+    fn unmove(&self, side: Player, dst: Pos, dir: Dir, jump: bool, preds: &mut Vec<(Game, Move)>) {
+        // a jumping piece can't be stopped by a normal move.
+        if self.jumping.is_some() && !jump {
+            return;
+        }
+
+        let back = dir.reverse();
+        let src = match jump {
+            true => back.jump(dst),
+            false => back.step(dst),
+        };
+        let Some(src) = src else {
+            return;
+        };
+        let gap = !(self.blk | self.red);
+        if !src.is(gap) {
+            return;
+        }
+
+        let m = Move { src, dir, jump };
+        let captured = match jump {
+            true => m.mid().board(),
+            false => Board::empty(),
+        };
+        if !(captured & !gap).is_empty() {
+            return;
+        }
+
+        let promotion = match side {
+            Player::BLK => Board::BLK_PROMOTION,
+            Player::RED => Board::RED_PROMOTION,
+        };
+        let was_kings = match () {
+            _ if !dst.is(self.king) => vec![false],
+            _ if dst.is(promotion) => vec![true, false],
+            _ => vec![true],
+        };
+        let captured_kings = match jump {
+            true => vec![false, true],
+            false => vec![false],
+        };
+        let jumpings = match jump {
+            true => vec![None, Some(src)],
+            false => vec![None],
+        };
+
+        for &was_king in &was_kings {
+            for &captured_king in &captured_kings {
+                for &jumping in &jumpings {
+                    let mut p = Game {
+                        side,
+                        jumping,
+                        passed: false,
+                        ..self.clone()
+                    };
+                    let (slf, opp) = match side {
+                        Player::BLK => (&mut p.blk, &mut p.red),
+                        Player::RED => (&mut p.red, &mut p.blk),
+                    };
+                    *slf = (*slf & !dst.board()) | src.board();
+                    *opp |= captured;
+                    p.king &= !dst.board();
+                    if was_king {
+                        p.king |= src.board();
+                    }
+                    if captured_king {
+                        p.king |= captured;
+                    }
+
+                    if p.validate().is_err() || !p.moves().any(|c| c == m) {
+                        continue;
+                    }
+                    if p.apply(&m) == *self {
+                        preds.push((p, m));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::testutil;
+    use crate::piece::{KING, PONE};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn predecessors_include_every_origin() {
+        let cases = [
+            ("Initial position", Game::initial()),
+            (
+                "Jumps, promotions and kings",
+                testutil::tactical(Player::BLK),
+            ),
+        ];
+
+        for (_, game) in cases {
+            testutil::walk(&game, 3, &mut |game| {
+                for m in game.moves() {
+                    let next = game.apply(&m);
+
+                    let preds = next.predecessors();
+                    assert_eq!(
+                        true,
+                        preds.contains(&(game.clone(), m)),
+                        "predecessors of {:?} by {:?}",
+                        next,
+                        m
+                    );
+                    for (p, pm) in &preds {
+                        assert_eq!(next, p.apply(pm), "apply {:?} on {:?}", pm, p);
+                    }
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn predecessors_uncapture_and_uncrown() {
+        let cases = [
+            (
+                "Black king crowned by a step or a jump",
+                testutil::game(
+                    Player::RED,
+                    None,
+                    r"
+                        _._B_._.
+                        ._._._._
+                        _._._._.
+                        ._._._._
+                        _._._._.
+                        ._._._._
+                        _._._._.
+                        ._._._R_
+                    ",
+                ),
+                vec![
+                    (2, 1, PONE, None, false),
+                    (2, 1, KING, None, false),
+                    (4, 1, PONE, None, false),
+                    (4, 1, KING, None, false),
+                    (1, 2, PONE, Some(PONE), false),
+                    (1, 2, PONE, Some(PONE), true),
+                    (1, 2, PONE, Some(KING), false),
+                    (1, 2, PONE, Some(KING), true),
+                    (1, 2, KING, Some(PONE), false),
+                    (1, 2, KING, Some(PONE), true),
+                    (1, 2, KING, Some(KING), false),
+                    (1, 2, KING, Some(KING), true),
+                    (5, 2, PONE, Some(PONE), false),
+                    (5, 2, PONE, Some(PONE), true),
+                    (5, 2, PONE, Some(KING), false),
+                    (5, 2, PONE, Some(KING), true),
+                    (5, 2, KING, Some(PONE), false),
+                    (5, 2, KING, Some(PONE), true),
+                    (5, 2, KING, Some(KING), false),
+                    (5, 2, KING, Some(KING), true),
+                ],
+            ),
+            (
+                "Black king in the middle of jumps",
+                testutil::game(
+                    Player::BLK,
+                    Pos::graphical(2, 5),
+                    r"
+                        _._._._.
+                        ._._._._
+                        _._._._.
+                        ._._._._
+                        _._r_._.
+                        ._B_._._
+                        _._._._.
+                        ._._._R_
+                    ",
+                ),
+                vec![
+                    (0, 3, KING, Some(PONE), false),
+                    (0, 3, KING, Some(PONE), true),
+                    (0, 3, KING, Some(KING), false),
+                    (0, 3, KING, Some(KING), true),
+                    (0, 7, KING, Some(PONE), false),
+                    (0, 7, KING, Some(PONE), true),
+                    (0, 7, KING, Some(KING), false),
+                    (0, 7, KING, Some(KING), true),
+                    (4, 7, KING, Some(PONE), false),
+                    (4, 7, KING, Some(PONE), true),
+                    (4, 7, KING, Some(KING), false),
+                    (4, 7, KING, Some(KING), true),
+                ],
+            ),
+        ];
+
+        // each predecessor is described by the source cell on the graphical board, the moved
+        // piece, the captured piece and whether the move continues a jump.
+        for (msg, game, expects) in cases {
+            let mut expects: Vec<_> = expects
+                .into_iter()
+                .map(|(gx, gy, piece, captured, jumping)| {
+                    (Pos::graphical(gx, gy).unwrap(), piece, captured, jumping)
+                })
+                .collect();
+            let mut actuals: Vec<_> = game
+                .predecessors()
+                .into_iter()
+                .map(|(p, m)| {
+                    let r = p.record(&m);
+                    (
+                        m.src,
+                        r.piece,
+                        r.captured.map(|(_, c)| c),
+                        p.jumping.is_some(),
+                    )
+                })
+                .collect();
+            expects.sort_by_key(|e| format!("{:?}", e));
+            actuals.sort_by_key(|a| format!("{:?}", a));
+
+            assert_eq!(expects, actuals, "{}", msg);
+        }
+    }
+}
@@ -0,0 +1,398 @@
+use crate::board::Board;
+use crate::dir::Dir;
+use crate::game::Game;
+use crate::goal::Goal;
+use crate::player::Player;
+use crate::turn::{Sequence, Turn};
+
+/// CapturePriority decides which capture sequences are allowed when there are several.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum CapturePriority {
+    /// any capture sequence can be chosen.
+    #[default]
+    Free,
+    /// only the sequences capturing the most pieces can be chosen.
+    Quantity,
+}
+
+/// Rules is a configuration of a checkers variant.
+/// every variant here is played on the 8x8 board, so they all share the 32-square Board.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Rules {
+    pub goal: Goal,
+    pub men_capture_backward: bool, // pones can capture backward too.
+    pub flying_kings: bool,         // kings move and capture over any distance.
+    pub priority: CapturePriority,
+}
+
+impl Rules {
+    /// AMERICAN is the English draughts, which this crate plays by default.
+    pub const AMERICAN: Rules = Rules {
+        goal: Goal::Normal,
+        men_capture_backward: false,
+        flying_kings: false,
+        priority: CapturePriority::Free,
+    };
+
+    /// GIVEAWAY is the American rules played for losing every piece.
+    pub const GIVEAWAY: Rules = Rules {
+        goal: Goal::Giveaway,
+        ..Rules::AMERICAN
+    };
+
+    /// POOL is the pool checkers, any capture sequence can be chosen.
+    pub const POOL: Rules = Rules {
+        goal: Goal::Normal,
+        men_capture_backward: true,
+        flying_kings: true,
+        priority: CapturePriority::Free,
+    };
+
+    /// BRAZILIAN is the international rules played on the 8x8 board instead of the 10x10 one,
+    /// so it differs from POOL only in that the longest capture must be chosen.
+    pub const BRAZILIAN: Rules = Rules {
+        priority: CapturePriority::Quantity,
+        ..Rules::POOL
+    };
+
+    /// winner() returns which player is winner of the game under these rules.
+    /// a player who can't move on its turn loses, or wins when playing giveaway.
+    /// if there are no winner, it returns None.
+    ///
+    /// Game::winner() disagrees on a blocked player: Game::apply() passes its turn instead,
+    /// so it doesn't lose there, while play() always gives the turn to the opponent.
+    pub fn winner(&self, g: &Game) -> Option<Player> {
+        if g.turns(self).next().is_some() {
+            return None;
+        }
+
+        match self.goal {
+            Goal::Normal => Some(!g.side),
+            Goal::Giveaway => Some(g.side),
+        }
+    }
+}
+
+impl Game {
+    /// turns() enumerates all next turn candidates under the rules.
+    /// capturing is mandatory, and a capture sequence is enumerated as a whole turn.
+    /// the search plays the American rules hop by hop with moves() instead.
+    pub fn turns(&self, rules: &Rules) -> impl Iterator<Item = Turn> {
+        let jumps = self.jump_turns(rules);
+        if jumps.is_empty() && self.jumping.is_none() {
+            self.step_turns(rules).into_iter()
+        } else {
+            jumps.into_iter()
+        }
+    }
+
+    /// play() applies a whole turn and passes the turn to the opponent.
+    pub fn play(&self, t: &Turn) -> Game {
+        let mut g = self.clone();
+        let king = &mut g.king;
+        let (slf, opp) = match self.side {
+            Player::BLK => (&mut g.blk, &mut g.red),
+            Player::RED => (&mut g.red, &mut g.blk),
+        };
+
+        let is_king = t.src.is(*king);
+
+        let src_mask = !t.src.board();
+        *slf &= src_mask;
+        *king &= src_mask;
+
+        *opp &= !t.captured;
+        *king &= !t.captured;
+
+        // pones are crowned only when the whole sequence finishes on the promotion row.
+        let dst_mask = t.dst().board();
+        let is_promotion = (dst_mask & Self::PROMOTION_MASK) != Board::empty();
+
+        *slf |= dst_mask;
+        if is_king || is_promotion {
+            *king |= dst_mask;
+        }
+
+        g.side = !g.side;
+        g.jumping = None;
+        g.passed = false;
+        g
+    }
+
+    /// perft() counts the leaf positions of the turn tree in the depth.
+    pub fn perft(&self, rules: &Rules, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let turns = self.turns(rules);
+        if depth == 1 {
+            return turns.count() as u64;
+        }
+
+        turns.map(|t| self.play(&t).perft(rules, depth - 1)).sum()
+    }
+
+    fn own(&self) -> (Board, Board) {
+        match self.side {
+            Player::BLK => (self.blk, self.red),
+            Player::RED => (self.red, self.blk),
+        }
+    }
+
+    fn step_turns(&self, rules: &Rules) -> Vec<Turn> {
+        let (slf, _) = self.own();
+        let occupied = self.red | self.blk;
+
+        let mut turns = vec![];
+        for src in slf.actives() {
+            let king = src.is(self.king);
+            let flying = king && rules.flying_kings;
+            for dir in Dir::ALL {
+                if !dir.valid_piece(self.side, king) {
+                    continue;
+                }
+
+                let mut cur = dir.step(src);
+                while let Some(pos) = cur {
+                    if pos.is(occupied) {
+                        break;
+                    }
+                    turns.push(Turn {
+                        src,
+                        path: vec![pos],
+                        captured: Board::empty(),
+                    });
+                    if !flying {
+                        break;
+                    }
+                    cur = dir.step(pos);
+                }
+            }
+        }
+
+        turns
+    }
+
+    fn jump_turns(&self, rules: &Rules) -> Vec<Turn> {
+        let (slf, opp) = self.own();
+        let srcs = match self.jumping {
+            Some(pos) => pos.board() & slf,
+            None => slf,
+        };
+
+        let mut found = vec![];
+        for src in srcs.actives() {
+            let king = src.is(self.king);
+            let backward = king || rules.men_capture_backward;
+            let dirs: Vec<Dir> = Dir::ALL
+                .into_iter()
+                .filter(|dir| backward || dir.valid_piece(self.side, king))
+                .collect();
+            let flying = king && rules.flying_kings;
+            Sequence::search(src, &dirs, flying, self.red | self.blk, opp, &mut found);
+        }
+
+        if rules.priority == CapturePriority::Quantity {
+            let most = found.iter().map(|t| t.captured.count()).max();
+            found.retain(|t| Some(t.captured.count()) == most);
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::testutil;
+    use crate::pos::Pos;
+    use pretty_assertions::assert_eq;
+
+    fn g(x: u8, y: u8) -> Pos {
+        Pos::graphical(x, y).unwrap()
+    }
+
+    fn sorted(turns: impl IntoIterator<Item = Turn>) -> Vec<(Pos, Vec<Pos>, Board)> {
+        let mut v: Vec<_> = turns
+            .into_iter()
+            .map(|t| (t.src, t.path, t.captured))
+            .collect();
+        v.sort_by_key(|(src, path, _)| (*src, path.clone()));
+        v
+    }
+
+    #[test]
+    fn perft_initial_position() {
+        let cases = [
+            ("American", Rules::AMERICAN, [7, 49, 302, 1469, 7361, 36768]),
+            ("Pool", Rules::POOL, [7, 49, 302, 1469, 7482, 37986]),
+            (
+                "Brazilian",
+                Rules::BRAZILIAN,
+                [7, 49, 302, 1469, 7473, 37628],
+            ),
+        ];
+
+        let game = Game::initial();
+        for (msg, rules, expects) in cases {
+            for (depth, expect) in expects.into_iter().enumerate() {
+                let actual = game.perft(&rules, depth as u32 + 1);
+                assert_eq!(expect, actual, "{}: depth {}", msg, depth + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn pones_capture_backward_only_in_pool_rules() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._b_._
+                _._._r_.
+                ._._._._
+                _._._._.
+                ._._._._
+            ",
+        );
+
+        let jump = Turn {
+            src: g(4, 3),
+            path: vec![g(6, 5)],
+            captured: g(5, 4).board(),
+        };
+
+        assert_eq!(false, game.turns(&Rules::AMERICAN).any(|t| t == jump));
+        assert_eq!(sorted([jump.clone()]), sorted(game.turns(&Rules::POOL)));
+        assert_eq!(sorted([jump]), sorted(game.turns(&Rules::BRAZILIAN)));
+    }
+
+    #[test]
+    fn kings_fly_only_in_pool_rules() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._r_._._
+                _._._._.
+                B_._._._
+            ",
+        );
+
+        let american = sorted([Turn {
+            src: g(0, 7),
+            path: vec![g(1, 6)],
+            captured: Board::empty(),
+        }]);
+        let pool = sorted((3..8).map(|v| Turn {
+            src: g(0, 7),
+            path: vec![g(v, 7 - v)],
+            captured: g(2, 5).board(),
+        }));
+
+        assert_eq!(american, sorted(game.turns(&Rules::AMERICAN)));
+        assert_eq!(pool, sorted(game.turns(&Rules::POOL)));
+    }
+
+    #[test]
+    fn brazilian_rules_force_the_longest_capture() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._r_._.
+                ._._._r_
+                _r_._._b
+                b_._._._
+            ",
+        );
+
+        let double = Turn {
+            src: g(0, 7),
+            path: vec![g(2, 5), g(4, 3)],
+            captured: g(1, 6).board() | g(3, 4).board(),
+        };
+        let single = Turn {
+            src: g(7, 6),
+            path: vec![g(5, 4)],
+            captured: g(6, 5).board(),
+        };
+
+        assert_eq!(
+            sorted([double.clone(), single]),
+            sorted(game.turns(&Rules::POOL)),
+            "Pool"
+        );
+        assert_eq!(
+            sorted([double]),
+            sorted(game.turns(&Rules::BRAZILIAN)),
+            "Brazilian"
+        );
+    }
+
+    #[test]
+    fn pones_are_crowned_only_at_the_end_of_sequence() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._r_r_
+                _._b_._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+            ",
+        );
+
+        let pool: Vec<Turn> = game.turns(&Rules::POOL).collect();
+        assert_eq!(
+            sorted([Turn {
+                src: g(3, 2),
+                path: vec![g(5, 0), g(7, 2)],
+                captured: g(4, 1).board() | g(6, 1).board(),
+            }]),
+            sorted(pool.clone())
+        );
+        assert_eq!(false, g(7, 2).is(game.play(&pool[0]).king), "Pool");
+
+        let american: Vec<Turn> = game.turns(&Rules::AMERICAN).collect();
+        assert_eq!(1, american.len());
+        assert_eq!(true, g(5, 0).is(game.play(&american[0]).king), "American");
+    }
+
+    #[test]
+    fn blocked_player_loses_or_wins_by_goal() {
+        let game = testutil::game(
+            Player::RED,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                r_._._._
+                _b_._._.
+                ._b_._._
+            ",
+        );
+
+        assert_eq!(Some(Player::BLK), Rules::AMERICAN.winner(&game));
+        assert_eq!(Some(Player::RED), Rules::GIVEAWAY.winner(&game));
+    }
+}
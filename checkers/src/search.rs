@@ -0,0 +1,1323 @@
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::game::Game;
+use crate::goal::Goal;
+use crate::mv::{Move, MoveList};
+use crate::ordering::{promotes, MoveOrdering};
+use crate::player::Player;
+use crate::ponder::PonderHit;
+use crate::rng::Rng;
+use crate::strength::Personality;
+use crate::table::{Bound, Entry, Table};
+use crate::time::{Clock, TimeManager};
+
+/// Score is an evaluation of a game from the side to move, in hundredths of a pone.
+pub type Score = i32;
+
+/// PONE_VALUE is the material value of a pone.
+pub const PONE_VALUE: Score = 100;
+/// KING_VALUE is the material value of a king.
+pub const KING_VALUE: Score = 130;
+/// WIN is the score of a won game. a win found in fewer plies scores higher.
+pub const WIN: Score = 1_000_000;
+/// INFINITY is greater than any score.
+pub const INFINITY: Score = WIN + 1;
+/// MAX_PLY bounds the plies of a search, so a score above WIN - MAX_PLY is a win found in
+/// WIN - score plies, and likewise for a loss.
+pub const MAX_PLY: Score = 1000;
+
+/// Options configures a Searcher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Options {
+    /// the outcome both players are playing for.
+    pub goal: Goal,
+    /// plies searched with every move.
+    pub depth: u32,
+    /// plies searched with captures only after `depth`, at most.
+    pub quiescence: u32,
+    /// try the hash move, promotions, killers and history moves first.
+    pub ordering: bool,
+    /// search moves after the first with a null window, re-searching on fail high.
+    pub pvs: bool,
+    /// search late quiet moves one ply shallower, re-searching on fail high.
+    pub reductions: bool,
+    /// skip quiet moves at the frontier which can't raise alpha.
+    pub futility: bool,
+    /// threads searching together. a single thread gives deterministic results.
+    pub threads: usize,
+    /// the clock of the side to move. with a clock, the search deepens up to `depth` while
+    /// the time allocated for the move lasts.
+    pub clock: Option<Clock>,
+    /// the style of the evaluation.
+    pub personality: Personality,
+    /// games searched by the calling thread, at most.
+    pub max_nodes: Option<u64>,
+    /// the most random score added to each move, so a worse move is played now and then.
+    /// with noise, every move is searched for its exact score on the calling thread.
+    pub noise: Score,
+    /// the seed of the random noise. the same seed plays the same moves.
+    pub seed: u64,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            goal: Goal::Normal,
+            depth: 6,
+            quiescence: 16,
+            ordering: true,
+            pvs: true,
+            reductions: true,
+            futility: true,
+            threads: 1,
+            clock: None,
+            personality: Personality::Balanced,
+            max_nodes: None,
+            noise: 0,
+            seed: 0,
+        }
+    }
+}
+
+/// SearchResult is the outcome of a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best: Option<Move>, // the best move. it will be None if the game has no move.
+    pub score: Score,       // the score of the best move from the side to move.
+    pub nodes: u64,         // the number of searched games.
+    pub reply: Option<Move>, // the expected first move of the opponent, to ponder on.
+}
+
+/// Line is a move of a game with its score and principal variation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    pub score: Score,  // the score of the move from the side to move.
+    pub pv: Vec<Move>, // the principal variation, starting with the move.
+}
+
+/// Progress is reported after each iteration of a search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub depth: u32,     // the depth of the iteration.
+    pub score: Score,   // the score of the best move from the side to move.
+    pub nodes: u64,     // the number of games searched by the calling thread so far.
+    pub nps: u64,       // the nodes searched per second.
+    pub time: Duration, // the time since the search started.
+    pub pv: Vec<Move>,  // the principal variation, starting with the best move.
+}
+
+/// StopHandle stops the search of a Searcher from another thread.
+/// the search returns the result of the last iteration it completed. a stop requested
+/// before the search starts stops it at once.
+#[derive(Debug, Clone)]
+pub struct StopHandle(Arc<AtomicBool>);
+
+impl StopHandle {
+    /// stop() asks the search to stop as soon as possible.
+    pub fn stop(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+type ProgressFn = Box<dyn FnMut(&Progress) + Send>;
+
+/// Searcher finds the best move by alpha-beta search.
+/// with several threads, helper threads search the same game at staggered depths and
+/// share what they learn through the transposition table (Lazy SMP).
+pub struct Searcher {
+    options: Options,
+    nodes: u64,
+    table: Arc<Table>,
+    ordering: MoveOrdering,
+    stop: Arc<AtomicBool>,        // aborts the search as soon as it's set.
+    time: Option<TimeManager>,    // the time of the move being searched, with a clock.
+    progress: Option<ProgressFn>, // called after each iteration.
+    hit: Option<Arc<PonderHit>>,  // set while pondering, until the ponder hit.
+    rng: Rng,                     // the noise of the moves, seeded by the options.
+    root: Player,                 // the side to move of the searched game.
+}
+
+impl fmt::Debug for Searcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Searcher")
+            .field("options", &self.options)
+            .field("nodes", &self.nodes)
+            .field("table", &self.table)
+            .field("ordering", &self.ordering)
+            .field("stop", &self.stop)
+            .field("time", &self.time)
+            .field("progress", &self.progress.is_some())
+            .field("hit", &self.hit)
+            .field("rng", &self.rng)
+            .field("root", &self.root)
+            .finish()
+    }
+}
+
+impl Searcher {
+    /// TABLE_BITS is the size of the transposition table, 2^TABLE_BITS entries.
+    const TABLE_BITS: u32 = 18;
+    /// LATE_MOVES is the number of moves searched without reductions.
+    const LATE_MOVES: usize = 3;
+    /// REDUCTION_DEPTH is the shallowest depth where late moves are reduced.
+    const REDUCTION_DEPTH: u32 = 3;
+    /// FUTILITY_MARGIN is the most a quiet move at the frontier is expected to win.
+    const FUTILITY_MARGIN: Score = 2 * PONE_VALUE;
+    /// CHECK_NODES is how often the clock is checked, in nodes.
+    const CHECK_NODES: u64 = 1024;
+
+    pub fn new(options: Options) -> Searcher {
+        Searcher {
+            options,
+            nodes: 0,
+            table: Arc::new(Table::new(Self::TABLE_BITS)),
+            ordering: MoveOrdering::new(),
+            stop: Arc::new(AtomicBool::new(false)),
+            time: None,
+            progress: None,
+            hit: None,
+            rng: Rng::new(options.seed),
+            root: Player::BLK,
+        }
+    }
+
+    /// stop_handle() returns a handle to stop the searches of the searcher.
+    pub fn stop_handle(&self) -> StopHandle {
+        StopHandle(self.stop.clone())
+    }
+
+    /// on_progress() sets the function called with the progress after each iteration.
+    /// send the progress through a channel to watch the search from another thread.
+    pub fn on_progress(&mut self, f: impl FnMut(&Progress) + Send + 'static) {
+        self.progress = Some(Box::new(f));
+    }
+
+    /// set_clock() sets the clock for the next searches, so a game is played move by move on
+    /// the same searcher as the clock runs down.
+    pub fn set_clock(&mut self, clock: Option<Clock>) {
+        self.options.clock = clock;
+    }
+
+    /// search() searches the game with the options and returns the best move.
+    /// with a clock, a game with a single move is answered without searching.
+    pub fn search(&mut self, g: &Game) -> SearchResult {
+        self.reset(g);
+        let result = match () {
+            _ if self.time.is_some() && g.moves().nth(1).is_none() => {
+                let lines = self.root(g, 0, 1);
+                self.result(g, &lines)
+            }
+            _ if self.options.noise > 0 => {
+                let lines = self.deepen(g, 1, usize::MAX);
+                self.pick(g, &lines)
+            }
+            _ if self.options.threads <= 1 => {
+                let lines = self.deepen(g, 1, 1);
+                self.result(g, &lines)
+            }
+            _ => self.smp(g),
+        };
+        self.stop.store(false, Ordering::Relaxed);
+        result
+    }
+
+    /// smp() searches the game with helper threads sharing the transposition table.
+    fn smp(&mut self, g: &Game) -> SearchResult {
+        let stop = Arc::new(AtomicBool::new(false));
+        thread::scope(|s| {
+            let helpers: Vec<_> = (1..self.options.threads)
+                .map(|i| {
+                    let mut helper = Searcher {
+                        options: self.options,
+                        nodes: 0,
+                        table: self.table.clone(),
+                        ordering: MoveOrdering::new(),
+                        stop: stop.clone(),
+                        time: None,
+                        progress: None,
+                        hit: None,
+                        rng: Rng::new(0),
+                        root: self.root,
+                    };
+                    // odd helpers start a ply deeper, so threads don't search in lockstep.
+                    s.spawn(move || {
+                        helper.deepen(g, 1 + i as u32 % 2, 1);
+                        helper.nodes
+                    })
+                })
+                .collect();
+
+            let lines = self.deepen(g, 1, 1);
+            stop.store(true, Ordering::Relaxed);
+
+            let nodes: u64 = helpers
+                .into_iter()
+                .map(|h| h.join().expect("a helper thread panicked"))
+                .sum();
+            let result = self.result(g, &lines);
+            SearchResult {
+                nodes: result.nodes + nodes,
+                ..result
+            }
+        })
+    }
+
+    /// analyze() searches the game with the options like search(), but returns the `n` best
+    /// moves with their scores and principal variations, the best first (multi-PV).
+    /// a game without moves has a single line without moves, scored as the game ends.
+    /// the analysis runs on the calling thread only.
+    pub fn analyze(&mut self, g: &Game, n: usize) -> Vec<Line> {
+        self.reset(g);
+        let lines = self.deepen(g, 1, n.max(1));
+        self.stop.store(false, Ordering::Relaxed);
+        lines
+    }
+
+    /// set_hit() makes the searcher ponder until the hit comes, or stops pondering.
+    pub(crate) fn set_hit(&mut self, hit: Option<Arc<PonderHit>>) {
+        self.hit = hit;
+    }
+
+    /// reset() prepares a new search of the game. a pondering search has no clock until the
+    /// hit.
+    fn reset(&mut self, g: &Game) {
+        self.nodes = 0;
+        self.root = g.side;
+        self.table.clear();
+        self.ordering.clear();
+        self.time = match self.hit {
+            Some(_) => None,
+            None => self.options.clock.as_ref().map(TimeManager::new),
+        };
+    }
+
+    /// poll_hit() starts the clock of a pondering search once the ponder hit comes.
+    fn poll_hit(&mut self) {
+        if let Some(clock) = self.hit.as_ref().and_then(|h| h.take()) {
+            self.time = clock.as_ref().map(TimeManager::new);
+            self.hit = None;
+        }
+    }
+
+    /// pick() returns the search result of the line with the highest score plus noise.
+    fn pick(&mut self, g: &Game, lines: &[Line]) -> SearchResult {
+        let noise = self.options.noise as u64 + 1;
+        let noisy: Vec<Score> = lines
+            .iter()
+            .map(|l| l.score + self.rng.below(noise) as Score)
+            .collect();
+        let i = (0..lines.len()).max_by_key(|&i| noisy[i]).unwrap_or(0);
+        self.result(g, &lines[i..])
+    }
+
+    /// result() returns the search result of the best line of the game.
+    fn result(&self, g: &Game, lines: &[Line]) -> SearchResult {
+        let pv = &lines[0].pv;
+        // the best move may be the first hop of a multi-jump, so the reply is the first move
+        // of the opponent.
+        let mut game = g.clone();
+        let mut reply = None;
+        for m in pv {
+            if game.side != g.side {
+                reply = Some(*m);
+                break;
+            }
+            game = game.apply(m);
+        }
+        SearchResult {
+            best: pv.first().copied(),
+            score: lines[0].score,
+            nodes: self.nodes,
+            reply,
+        }
+    }
+
+    /// deepen() searches the n best lines of the game from the depth to the depth of the
+    /// options one by one, so each iteration tries the best moves of the previous one first.
+    /// it returns the lines of the last iteration which wasn't stopped.
+    /// with a clock, no iteration is started after the soft limit.
+    fn deepen(&mut self, g: &Game, from: u32, n: usize) -> Vec<Line> {
+        let start = Instant::now();
+        let mut lines = self.root(g, 0, n);
+        for depth in from..=self.options.depth {
+            self.poll_hit();
+            if depth > from && self.time.as_ref().is_some_and(|t| !t.can_deepen()) {
+                break;
+            }
+            let next = self.root(g, depth, n);
+            if self.stopped() {
+                break;
+            }
+            if let Some(t) = &mut self.time {
+                t.update(
+                    depth > from && next[0].pv.first() != lines[0].pv.first(),
+                    lines[0].score - next[0].score,
+                );
+            }
+            lines = next;
+            self.report(depth, &lines[0], start.elapsed());
+        }
+        lines
+    }
+
+    /// report() calls the progress function with the best line of the iteration.
+    fn report(&mut self, depth: u32, line: &Line, time: Duration) {
+        if let Some(f) = &mut self.progress {
+            f(&Progress {
+                depth,
+                score: line.score,
+                nodes: self.nodes,
+                nps: (self.nodes as f64 / time.as_secs_f64().max(1e-6)) as u64,
+                time,
+                pv: line.pv.clone(),
+            });
+        }
+    }
+
+    /// pv() follows the best moves stored in the transposition table from the game, for
+    /// `depth` plies. the variation is cut short where an entry was replaced.
+    fn pv(&self, g: &Game, depth: u32) -> Vec<Move> {
+        let mut pv = vec![];
+        let mut g = g.clone();
+        let mut depth = depth;
+        while depth > 0 {
+            let Some(m) = self.table.get(g.key()).and_then(|e| e.mv) else {
+                break;
+            };
+            if !g.moves().any(|legal| legal == m) {
+                break;
+            }
+            let next = g.apply(&m);
+            if next.jumping.is_none() {
+                depth -= 1;
+            }
+            pv.push(m);
+            g = next;
+        }
+        pv
+    }
+
+    fn stopped(&self) -> bool {
+        self.stop.load(Ordering::Relaxed)
+    }
+
+    /// check_limits() stops the search once the nodes or the hard limit of the clock have
+    /// run out. a pondering search checks for the ponder hit as often as the clock.
+    fn check_limits(&mut self) {
+        if self.options.max_nodes.is_some_and(|max| self.nodes >= max) {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+        if !self.nodes.is_multiple_of(Self::CHECK_NODES) {
+            return;
+        }
+        self.poll_hit();
+        if self.time.as_ref().is_some_and(|t| t.expired()) {
+            self.stop.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// root() searches the game `depth` plies and returns the n best lines.
+    /// a move only needs an exact score if it beats the n-th best line so far, so the moves
+    /// are searched with the score of that line as alpha once n lines are found.
+    fn root(&mut self, g: &Game, depth: u32, n: usize) -> Vec<Line> {
+        let key = g.key();
+        let mut lines: Vec<Line> = vec![];
+        for (i, m) in self.ordered_moves(g, key, 0).into_iter().enumerate() {
+            let full = lines.len() == n;
+            let alpha = match full {
+                true => lines[n - 1].score,
+                false => -INFINITY,
+            };
+            let i = if full { i } else { 0 };
+            let score = self.search_move(g, key, m, i, depth, 0, alpha, INFINITY);
+            // quiescence isn't stopped, so a stopped search still has a move to play.
+            if depth > 0 && self.stopped() {
+                break;
+            }
+            if full && score <= alpha {
+                continue;
+            }
+
+            let next = g.apply(&m);
+            let rest = match next.jumping {
+                Some(_) => depth,
+                None => depth.saturating_sub(1),
+            };
+            let mut pv = vec![m];
+            pv.extend(self.pv(&next, rest));
+            let at = lines.partition_point(|l| l.score >= score);
+            lines.insert(at, Line { score, pv });
+            lines.truncate(n);
+        }
+
+        if lines.is_empty() {
+            let score = self.quiescence(g, 0, self.options.quiescence, -INFINITY, INFINITY);
+            return vec![Line { score, pv: vec![] }];
+        }
+        if depth == 0 || !self.stopped() {
+            let best = &lines[0];
+            self.store(
+                key,
+                0,
+                depth,
+                best.score,
+                best.pv.first().copied(),
+                Bound::Exact,
+            );
+        }
+        lines
+    }
+
+    /// alphabeta() returns the score of the game searched `depth` plies more.
+    /// the pieces jumping in a sequence are searched without reducing the depth, so a
+    /// capture sequence is never cut at the horizon. the key is the one of the game.
+    fn alphabeta(
+        &mut self,
+        g: &Game,
+        key: u64,
+        depth: u32,
+        ply: u32,
+        alpha: Score,
+        beta: Score,
+    ) -> Score {
+        if depth == 0 {
+            return self.quiescence(g, ply, self.options.quiescence, alpha, beta);
+        }
+        self.nodes += 1;
+        self.check_limits();
+        if self.stopped() {
+            return 0;
+        }
+
+        if let Some(score) = self.terminal(g, ply) {
+            return score;
+        }
+        if let Some(score) = self.probe(key, depth, ply, alpha, beta) {
+            return score;
+        }
+        if let Some(score) = self.futile(g, depth, alpha) {
+            return score;
+        }
+
+        let mut alpha = alpha;
+        let mut best = None;
+        let mut bound = Bound::Upper;
+        for (i, m) in self.ordered_moves(g, key, ply).into_iter().enumerate() {
+            let score = self.search_move(g, key, m, i, depth, ply, alpha, beta);
+            if self.stopped() {
+                return 0;
+            }
+            if score >= beta {
+                if self.options.ordering {
+                    self.ordering.cutoff(m, ply, depth);
+                }
+                self.store(key, ply, depth, score, Some(m), Bound::Lower);
+                return score;
+            }
+            if score > alpha {
+                alpha = score;
+                best = Some(m);
+                bound = Bound::Exact;
+            }
+        }
+        self.store(key, ply, depth, alpha, best, bound);
+        alpha
+    }
+
+    /// probe() returns the score of the game from the transposition table, if it was
+    /// searched deep enough and the score decides the window.
+    fn probe(&self, key: u64, depth: u32, ply: u32, alpha: Score, beta: Score) -> Option<Score> {
+        let e = self.table.get(key)?;
+        if e.depth < depth {
+            return None;
+        }
+        let score = from_table(e.score, ply);
+        match e.bound {
+            Bound::Exact => Some(score),
+            Bound::Lower if score >= beta => Some(score),
+            Bound::Upper if score <= alpha => Some(score),
+            _ => None,
+        }
+    }
+
+    /// store() records the result of the search of the game in the transposition table.
+    fn store(&self, key: u64, ply: u32, depth: u32, score: Score, mv: Option<Move>, bound: Bound) {
+        self.table.put(
+            key,
+            Entry {
+                mv,
+                score: to_table(score, ply),
+                depth,
+                bound,
+            },
+        );
+    }
+
+    /// search_move() searches the i-th move of the game and returns its score.
+    /// with pvs, moves after the first are expected to fail low and searched with a null
+    /// window. with reductions, late quiet moves are searched a ply shallower. either search
+    /// is repeated with the full window and depth when the move turns out to raise alpha.
+    #[allow(clippy::too_many_arguments)]
+    fn search_move(
+        &mut self,
+        g: &Game,
+        key: u64,
+        m: Move,
+        i: usize,
+        depth: u32,
+        ply: u32,
+        alpha: Score,
+        beta: Score,
+    ) -> Score {
+        let next = g.apply(&m);
+        let key = g.rekey(key, &next);
+
+        let reduce = self.options.reductions
+            && i >= Self::LATE_MOVES
+            && depth >= Self::REDUCTION_DEPTH
+            && !m.jump
+            && !promotes(g, &m);
+        let reduced = if reduce { depth - 1 } else { depth };
+        let null = self.options.pvs && i > 0 && alpha + 1 < beta;
+        let window = if null {
+            (alpha, alpha + 1)
+        } else {
+            (alpha, beta)
+        };
+
+        let mut score = self.child(g, &next, key, reduced, ply + 1, window.0, window.1);
+        if reduce && score > alpha {
+            score = self.child(g, &next, key, depth, ply + 1, window.0, window.1);
+        }
+        if null && score > alpha && score < beta {
+            score = self.child(g, &next, key, depth, ply + 1, alpha, beta);
+        }
+        score
+    }
+
+    /// futile() returns a fail low score, if the game is quiet at the frontier and even
+    /// winning a pone or two by a shot can't raise alpha. it returns None if the game has
+    /// to be searched.
+    fn futile(&self, g: &Game, depth: u32, alpha: Score) -> Option<Score> {
+        let quiet = g.jumping.is_none() && g.jumpables().is_empty();
+        let decisive = alpha.abs() >= WIN - MAX_PLY;
+        if !self.options.futility || depth != 1 || !quiet || decisive {
+            return None;
+        }
+
+        let margin = self.evaluate(g) + Self::FUTILITY_MARGIN;
+        (margin <= alpha).then_some(margin)
+    }
+
+    /// ordered_moves() returns the moves of the game at the ply, in the order to search.
+    fn ordered_moves(&self, g: &Game, key: u64, ply: u32) -> MoveList {
+        let mut moves = g.move_list();
+        if self.options.ordering {
+            let hash = self.table.get(key).and_then(|e| e.mv);
+            self.ordering.order(g, &mut moves, hash, ply);
+        }
+        moves
+    }
+
+    /// quiescence() resolves the pending captures of the game and returns its score.
+    /// while the side to move can jump it has to, so the static evaluation is trusted only
+    /// after the exchange is over, or after `depth` plies of captures.
+    fn quiescence(&mut self, g: &Game, ply: u32, depth: u32, alpha: Score, beta: Score) -> Score {
+        self.nodes += 1;
+
+        if let Some(score) = self.terminal(g, ply) {
+            return score;
+        }
+        let quiet = g.jumping.is_none() && g.jumpables().is_empty();
+        if quiet || depth == 0 {
+            return self.evaluate(g);
+        }
+
+        let mut alpha = alpha;
+        for m in g.moves() {
+            let next = g.apply(&m);
+            let score = match next.side == g.side {
+                true => self.quiescence(&next, ply + 1, depth - 1, alpha, beta),
+                false => -self.quiescence(&next, ply + 1, depth - 1, -beta, -alpha),
+            };
+            if score >= beta {
+                return score;
+            }
+            alpha = alpha.max(score);
+        }
+        alpha
+    }
+
+    /// child() searches the next game, of the key, from the side to move of the game.
+    /// the side keeps moving while jumping or when the opponent is passed, so the score is
+    /// negated only when the side changes.
+    #[allow(clippy::too_many_arguments)]
+    fn child(
+        &mut self,
+        g: &Game,
+        next: &Game,
+        key: u64,
+        depth: u32,
+        ply: u32,
+        alpha: Score,
+        beta: Score,
+    ) -> Score {
+        let depth = match next.jumping {
+            Some(_) => depth,
+            None => depth.saturating_sub(1),
+        };
+        match next.side == g.side {
+            true => self.alphabeta(next, key, depth, ply, alpha, beta),
+            false => -self.alphabeta(next, key, depth, ply, -beta, -alpha),
+        }
+    }
+
+    /// evaluate() returns the static score of the game with the personality of the options,
+    /// which plays for the side to move of the searched game.
+    fn evaluate(&self, g: &Game) -> Score {
+        self.options
+            .personality
+            .evaluate(g, self.options.goal, self.root)
+    }
+
+    /// terminal() returns the score of a finished game. it returns None if the game goes on.
+    fn terminal(&self, g: &Game, ply: u32) -> Option<Score> {
+        let winner = g.winner_for(self.options.goal)?;
+        let score = WIN - ply as Score;
+        Some(if winner == g.side { score } else { -score })
+    }
+}
+
+/// to_table() converts the score at the ply into the score at the game, so a win is
+/// stored by the distance from the game and stays valid wherever the game is found.
+fn to_table(score: Score, ply: u32) -> Score {
+    match score {
+        _ if score >= WIN - MAX_PLY => score + ply as Score,
+        _ if score <= -(WIN - MAX_PLY) => score - ply as Score,
+        _ => score,
+    }
+}
+
+/// from_table() converts the score stored by to_table() back into the score at the ply.
+fn from_table(score: Score, ply: u32) -> Score {
+    match score {
+        _ if score >= WIN - MAX_PLY => score - ply as Score,
+        _ if score <= -(WIN - MAX_PLY) => score + ply as Score,
+        _ => score,
+    }
+}
+
+/// evaluate() returns the static score of the game from the side to move.
+/// pieces are a burden under the giveaway goal, so the material is counted negatively.
+pub fn evaluate(g: &Game, goal: Goal) -> Score {
+    Personality::Balanced.evaluate(g, goal, g.side)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::board::Board;
+    use crate::game::testutil;
+    use crate::player::Player;
+    use crate::pos::Pos;
+    use crate::suite;
+    use pretty_assertions::assert_eq;
+    use std::sync::mpsc;
+
+    #[test]
+    fn quiescence_resolves_two_for_one_shot() {
+        let shot = suite::two_for_one()
+            .moves()
+            .find(|m| Pos::graphical(2, 3) == Some(m.dst()))
+            .unwrap();
+
+        let cases = [
+            ("Black shot", suite::two_for_one(), Some(shot)),
+            ("Red shot", suite::two_for_one().flip(), Some(shot.flip())),
+        ];
+
+        for (msg, game, expect) in cases {
+            let options = Options {
+                depth: 1,
+                ..Options::default()
+            };
+            let actual = Searcher::new(options).search(&game);
+            assert_eq!(expect, actual.best, "{}", msg);
+            assert_eq!(PONE_VALUE, actual.score, "{}", msg);
+
+            let horizon = Options {
+                quiescence: 0,
+                ..options
+            };
+            let actual = Searcher::new(horizon).search(&game);
+            assert_eq!(0, actual.score, "{} without quiescence", msg);
+        }
+    }
+
+    #[test]
+    fn ordering_reduces_nodes() {
+        let mut nodes = [0, 0];
+        for (msg, game) in suite::games() {
+            let ordered = Options {
+                depth: 6,
+                pvs: false,
+                reductions: false,
+                futility: false,
+                ..Options::default()
+            };
+            let unordered = Options {
+                ordering: false,
+                ..ordered
+            };
+            let expect = Searcher::new(unordered).search(&game);
+            let actual = Searcher::new(ordered).search(&game);
+
+            assert_eq!(expect.score, actual.score, "{}", msg);
+            nodes[0] += expect.nodes;
+            nodes[1] += actual.nodes;
+        }
+
+        assert_eq!(true, nodes[1] < nodes[0], "{:?}", nodes);
+    }
+
+    /// tactics() returns games with a single best move, which wins at least a pone more than
+    /// any other move by a full width search.
+    fn tactics() -> Vec<(&'static str, Game, &'static str)> {
+        vec![
+            (
+                "Red wins a pone by 12-8",
+                testutil::game(
+                    Player::RED,
+                    None,
+                    r"
+                        _r_r_._r
+                        r_r_r_r_
+                        _._r_r_r
+                        b_._._._
+                        _._._b_b
+                        r_b_._._
+                        _._b_b_.
+                        b_b_b_b_
+                    ",
+                ),
+                "12-8",
+            ),
+            (
+                "Red crowns by 22-17",
+                testutil::game(
+                    Player::RED,
+                    None,
+                    r"
+                        _r_._B_r
+                        r_._._r_
+                        _._._r_r
+                        ._._._._
+                        _r_b_._b
+                        R_._b_._
+                        _._b_b_.
+                        ._._._b_
+                    ",
+                ),
+                "22-17",
+            ),
+            (
+                "Black saves a pone by 13-17",
+                testutil::game(
+                    Player::BLK,
+                    None,
+                    r"
+                        _r_r_r_r
+                        ._r_r_._
+                        _r_._r_b
+                        ._._._._
+                        _._r_._b
+                        ._._._._
+                        _b_._b_b
+                        b_b_b_b_
+                    ",
+                ),
+                "13-17",
+            ),
+            (
+                "Black wins a pone by 13-17",
+                testutil::game(
+                    Player::BLK,
+                    None,
+                    r"
+                        _r_r_._r
+                        ._._._._
+                        _r_._r_.
+                        ._._r_._
+                        _._r_._b
+                        b_._._._
+                        _._._b_b
+                        b_b_b_b_
+                    ",
+                ),
+                "13-17",
+            ),
+            (
+                "Red wins a king by 31-26",
+                testutil::game(
+                    Player::RED,
+                    None,
+                    r"
+                        _r_r_B_r
+                        r_r_._r_
+                        _._._._r
+                        r_._._._
+                        _._b_._.
+                        r_._b_b_
+                        _b_b_._.
+                        b_b_._b_
+                    ",
+                ),
+                "31-26",
+            ),
+            (
+                "Red wins a pone by 20-16",
+                testutil::game(
+                    Player::RED,
+                    None,
+                    r"
+                        _r_r_B_r
+                        r_r_._._
+                        _._._r_r
+                        r_._._._
+                        _._b_._.
+                        r_b_b_b_
+                        _._b_._.
+                        b_b_._b_
+                    ",
+                ),
+                "20-16",
+            ),
+        ]
+    }
+
+    #[test]
+    fn tactics_are_solved_with_every_option() {
+        let plain = Options {
+            depth: 6,
+            ordering: false,
+            pvs: false,
+            reductions: false,
+            futility: false,
+            ..Options::default()
+        };
+        let options = [
+            ("plain", plain),
+            (
+                "ordering",
+                Options {
+                    ordering: true,
+                    ..plain
+                },
+            ),
+            ("pvs", Options { pvs: true, ..plain }),
+            (
+                "reductions",
+                Options {
+                    reductions: true,
+                    ..plain
+                },
+            ),
+            (
+                "futility",
+                Options {
+                    futility: true,
+                    ..plain
+                },
+            ),
+            (
+                "all",
+                Options {
+                    depth: 6,
+                    ..Options::default()
+                },
+            ),
+        ];
+
+        for (msg, game, expect) in tactics() {
+            for (name, options) in options {
+                let actual = Searcher::new(options).search(&game);
+                assert_eq!(
+                    Some(expect.to_string()),
+                    actual.best.map(|m| m.to_string()),
+                    "{} with {}",
+                    msg,
+                    name
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pruning_reduces_nodes() {
+        let exact = Options {
+            depth: 8,
+            pvs: false,
+            reductions: false,
+            futility: false,
+            ..Options::default()
+        };
+        let mut nodes = [0, 0];
+        for (_, game) in suite::games() {
+            nodes[0] += Searcher::new(exact).search(&game).nodes;
+            nodes[1] += Searcher::new(Options {
+                depth: 8,
+                ..Options::default()
+            })
+            .search(&game)
+            .nodes;
+        }
+
+        assert_eq!(true, nodes[1] < nodes[0], "{:?}", nodes);
+    }
+
+    #[test]
+    fn single_thread_is_deterministic() {
+        for (msg, game) in suite::games() {
+            let options = Options {
+                depth: 7,
+                ..Options::default()
+            };
+            let mut searcher = Searcher::new(options);
+
+            let expect = searcher.search(&game);
+            let actual = searcher.search(&game);
+
+            assert_eq!(expect, actual, "{}", msg);
+            assert_eq!(expect, Searcher::new(options).search(&game), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn threads_solve_tactics() {
+        let options = Options {
+            depth: 6,
+            threads: 4,
+            ..Options::default()
+        };
+
+        for (msg, game, expect) in tactics() {
+            let actual = Searcher::new(options).search(&game);
+            assert_eq!(
+                Some(expect.to_string()),
+                actual.best.map(|m| m.to_string()),
+                "{}",
+                msg
+            );
+        }
+    }
+
+    #[test]
+    fn search_finds_win() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._r_._.
+                ._b_._._
+                _._._._.
+                ._._._._
+            ",
+        );
+
+        for goal in [Goal::Normal, Goal::Giveaway] {
+            let options = Options {
+                goal,
+                depth: 2,
+                ..Options::default()
+            };
+            let actual = Searcher::new(options).search(&game);
+            let expect = match goal {
+                Goal::Normal => WIN - 1,
+                Goal::Giveaway => -(WIN - 1),
+            };
+            assert_eq!(expect, actual.score, "{:?}", goal);
+        }
+    }
+
+    #[test]
+    fn giveaway_does_not_block_the_opponent() {
+        let board = |squares: &[u8]| {
+            squares.iter().fold(Board::empty(), |b, &n| {
+                b | Pos::from_square(n).unwrap().board()
+            })
+        };
+        let game = Game {
+            side: Player::BLK,
+            blk: board(&[5, 6]),
+            red: board(&[13]),
+            ..Default::default()
+        };
+
+        let options = Options {
+            goal: Goal::Giveaway,
+            depth: 3,
+            ..Options::default()
+        };
+        let actual = Searcher::new(options).search(&game);
+
+        // 5-9 leaves Red without a move, which wins the giveaway game for Red.
+        assert_ne!(Some(testutil::step(&game, 5, 9)), actual.best);
+        assert!(actual.score < WIN - 10, "{}", actual.score);
+    }
+
+    #[test]
+    fn clock_limits_search() {
+        let clock = Clock {
+            remaining: Duration::from_secs(3),
+            increment: Duration::ZERO,
+            moves_to_go: None,
+        };
+        let single = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._r_._.
+                ._b_._._
+                _._._._.
+                ._._._._
+            ",
+        );
+        let cases = [
+            ("Initial", Game::initial(), u64::MAX, true),
+            ("Single move", single, 10, false),
+        ];
+
+        for (msg, game, nodes, deepened) in cases {
+            let (tx, rx) = mpsc::channel();
+            let mut searcher = Searcher::new(Options {
+                depth: 64,
+                clock: Some(clock),
+                ..Options::default()
+            });
+            searcher.on_progress(move |p| tx.send(p.depth).unwrap());
+            let actual = searcher.search(&game);
+            let depths: Vec<u32> = rx.try_iter().collect();
+
+            assert_eq!(
+                Some(TimeManager::new(&clock).hard()),
+                searcher.time.as_ref().map(|t| t.hard()),
+                "{}",
+                msg
+            );
+            // the clock stops the search long before the depth of the options.
+            assert_eq!(deepened, !depths.is_empty(), "{}", msg);
+            assert!(depths.iter().all(|&d| d < 64), "{}: {:?}", msg, depths);
+            assert!(actual.best.is_some(), "{}", msg);
+            assert!(actual.nodes <= nodes, "{}: {}", msg, actual.nodes);
+        }
+    }
+
+    #[test]
+    fn reply_follows_multi_jump() {
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                r_._._._
+                _._._._.
+                ._._r_._
+                _._._._.
+                ._r_._._
+                _b_._._.
+                ._._._._
+            ",
+        );
+
+        let result = Searcher::new(Options {
+            depth: 4,
+            ..Options::default()
+        })
+        .search(&game);
+
+        assert_eq!(Pos::graphical(1, 6), result.best.map(|m| m.src));
+        assert_eq!(Pos::graphical(0, 1), result.reply.map(|m| m.src));
+    }
+
+    #[test]
+    fn clock_is_set_for_each_move() {
+        let clock = |ms| Clock {
+            remaining: Duration::from_millis(ms),
+            increment: Duration::ZERO,
+            moves_to_go: None,
+        };
+        let mut searcher = Searcher::new(Options {
+            depth: 64,
+            ..Options::default()
+        });
+        let cases = [("First move", clock(3000)), ("Low on time", clock(120))];
+
+        let mut game = Game::initial();
+        for (msg, clock) in cases {
+            searcher.set_clock(Some(clock));
+            let actual = searcher.search(&game);
+
+            assert_eq!(
+                Some(TimeManager::new(&clock).hard()),
+                searcher.time.as_ref().map(|t| t.hard()),
+                "{}",
+                msg
+            );
+            game = game.apply(&actual.best.expect(msg));
+        }
+    }
+
+    #[test]
+    fn progress_reports_each_iteration() {
+        let (tx, rx) = mpsc::channel();
+        let mut searcher = Searcher::new(Options {
+            depth: 5,
+            ..Options::default()
+        });
+        searcher.on_progress(move |p| tx.send(p.clone()).unwrap());
+        let result = searcher.search(&Game::initial());
+        let reports: Vec<Progress> = rx.try_iter().collect();
+
+        let depths: Vec<u32> = reports.iter().map(|p| p.depth).collect();
+        assert_eq!(vec![1, 2, 3, 4, 5], depths);
+        for pair in reports.windows(2) {
+            assert!(pair[0].nodes < pair[1].nodes, "{:?}", pair);
+        }
+        for p in &reports {
+            assert!(
+                !p.pv.is_empty() && p.pv.len() <= p.depth as usize,
+                "{:?}",
+                p
+            );
+            let mut game = Game::initial();
+            for m in &p.pv {
+                game = game.try_apply(m).unwrap();
+            }
+        }
+        let last = reports.last().unwrap();
+        assert_eq!(
+            (result.score, result.best),
+            (last.score, last.pv.first().copied())
+        );
+    }
+
+    #[test]
+    fn analyze_returns_best_lines() {
+        let exact = Options {
+            depth: 5,
+            pvs: false,
+            reductions: false,
+            futility: false,
+            ..Options::default()
+        };
+        let game = Game::initial();
+        let all = Searcher::new(exact).analyze(&game, 7);
+        assert_eq!(7, all.len());
+
+        for n in [1, 3, 10] {
+            let lines = Searcher::new(exact).analyze(&game, n);
+            let scores: Vec<Score> = lines.iter().map(|l| l.score).collect();
+            let expect: Vec<Score> = all.iter().take(n).map(|l| l.score).collect();
+            assert_eq!(expect, scores, "{} lines", n);
+
+            for (i, line) in lines.iter().enumerate() {
+                let seen = lines[..i].iter().any(|l| l.pv[0] == line.pv[0]);
+                assert!(!seen, "{} lines: {:?}", n, line);
+                let mut g = game.clone();
+                for m in &line.pv {
+                    g = g.try_apply(m).unwrap();
+                }
+            }
+        }
+
+        let best = Searcher::new(exact).search(&game);
+        assert_eq!((best.score, best.best), (all[0].score, Some(all[0].pv[0])));
+    }
+
+    #[test]
+    fn analyze_game_without_moves() {
+        let game = testutil::game(
+            Player::RED,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._._._._
+                _._._._.
+                ._b_._._
+            ",
+        );
+        let lines = Searcher::new(Options::default()).analyze(&game, 3);
+        assert_eq!(
+            vec![Line {
+                score: -WIN,
+                pv: vec![]
+            }],
+            lines
+        );
+    }
+
+    #[test]
+    fn stop_handle_stops_search() {
+        let mut searcher = Searcher::new(Options {
+            depth: 64,
+            ..Options::default()
+        });
+        let handle = searcher.stop_handle();
+        let (tx, rx) = mpsc::channel();
+        searcher.on_progress(move |p| tx.send(p.depth).unwrap());
+
+        let (result, depths) = thread::scope(|s| {
+            let search = s.spawn(|| searcher.search(&Game::initial()));
+            // the search of depth 64 never ends unless it's stopped.
+            let depths: Vec<u32> = rx.iter().take_while(|&d| d < 3).collect();
+            handle.stop();
+            (search.join().unwrap(), depths)
+        });
+
+        assert_eq!(vec![1, 2], depths);
+        assert!(result.best.is_some());
+    }
+
+    #[test]
+    fn evaluate_counts_material_for_goal() {
+        let game = testutil::game(
+            Player::RED,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._._R_.
+                ._._._._
+                _._r_._.
+                ._b_._._
+                _._._._.
+                ._._._._
+            ",
+        );
+
+        let cases = [
+            ("Normal", Goal::Normal, KING_VALUE),
+            ("Giveaway", Goal::Giveaway, -KING_VALUE),
+        ];
+
+        for (msg, goal, expect) in cases {
+            assert_eq!(expect, evaluate(&game, goal), "{}", msg);
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use crate::board::Board;
+use crate::game::Game;
+use crate::goal::Goal;
+use crate::player::Player;
+use crate::search::{Options, Score, KING_VALUE, PONE_VALUE};
+
+/// Personality is the playing style of the engine, which weighs its evaluation.
+#[derive(Debug, Default, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Personality {
+    /// counts the material only.
+    #[default]
+    Balanced,
+    /// values taking opponent pieces over keeping its own, and pushes its pones forward.
+    Aggressive,
+    /// values keeping its own pieces over taking opponent pieces, and guards its home row.
+    Defensive,
+}
+
+/// Weights are the terms of the evaluation of a Personality.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Weights {
+    own: Score,      // the percentage of the value of the own material.
+    opponent: Score, // the percentage of the value of the opponent material.
+    advance: Score,  // the bonus for each row a pone has advanced.
+    home: Score,     // the bonus for each pone on its home row.
+}
+
+impl Personality {
+    /// evaluate() returns the static score of the game from the side to move. the style is
+    /// the one of the root player, who the engine is playing for, so the score keeps its
+    /// weights whichever side is to move at the leaf.
+    pub fn evaluate(self, g: &Game, goal: Goal, root: Player) -> Score {
+        let w = self.weights();
+        let (slf, opp) = match root {
+            Player::BLK => (g.blk, g.red),
+            Player::RED => (g.red, g.blk),
+        };
+        let value = |b: Board| {
+            (b & !g.king).count() as Score * PONE_VALUE + (b & g.king).count() as Score * KING_VALUE
+        };
+        let shape = |side: Player, b: Board| {
+            let pones = b & !g.king;
+            // the rows from the home row of the side.
+            let rows = match side {
+                Player::BLK => Board::ROWS,
+                Player::RED => Board::ROWS.map(Board::rotate),
+            };
+            let advance: Score = (1..8)
+                .map(|y| (pones & rows[y]).count() as Score * y as Score)
+                .sum();
+            advance * w.advance + (pones & rows[0]).count() as Score * w.home
+        };
+
+        let score = (value(slf) * w.own - value(opp) * w.opponent) / 100 + shape(root, slf)
+            - shape(!root, opp);
+        let score = if g.side == root { score } else { -score };
+        match goal {
+            Goal::Normal => score,
+            Goal::Giveaway => -score,
+        }
+    }
+
+    fn weights(self) -> Weights {
+        match self {
+            Personality::Balanced => Weights {
+                own: 100,
+                opponent: 100,
+                advance: 0,
+                home: 0,
+            },
+            Personality::Aggressive => Weights {
+                own: 100,
+                opponent: 120,
+                advance: 3,
+                home: 0,
+            },
+            Personality::Defensive => Weights {
+                own: 120,
+                opponent: 100,
+                advance: 0,
+                home: 10,
+            },
+        }
+    }
+}
+
+/// Strength limits the play of the engine to about a rating, for weaker opponents.
+/// the engine searches shallower with fewer nodes, and picks a worse move now and then.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Strength {
+    pub rating: u32,
+}
+
+impl Strength {
+    /// WEAKEST is the lowest rating the engine plays at.
+    pub const WEAKEST: u32 = 800;
+    /// FULL is the rating the engine plays at full strength.
+    pub const FULL: u32 = 2400;
+    /// STEP is the rating gained for each ply searched deeper.
+    const STEP: u32 = 200;
+
+    /// options() returns the options limited to the rating.
+    pub fn options(self, options: Options) -> Options {
+        let rating = self.rating.clamp(Self::WEAKEST, Self::FULL);
+        if rating == Self::FULL {
+            return options;
+        }
+
+        let steps = (rating - Self::WEAKEST) / Self::STEP;
+        Options {
+            depth: options.depth.min(1 + steps),
+            max_nodes: Some(500 * 3u64.pow(steps)),
+            noise: (Self::FULL - rating) as Score / 4,
+            ..options
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::testutil;
+    use crate::search::{evaluate, Searcher};
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn personalities_weigh_evaluation() {
+        // black has a pone 5 rows up, and a pone and a king at home. red has pones 1 and 4
+        // rows up.
+        let game = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._r_._._
+                _b_._._.
+                ._._._._
+                _._._r_.
+                ._._._._
+                _._._._.
+                ._B_b_._
+            ",
+        );
+
+        let cases = [
+            ("Balanced", Personality::Balanced, Goal::Normal, 130),
+            ("Aggressive", Personality::Aggressive, Goal::Normal, 90),
+            ("Defensive", Personality::Defensive, Goal::Normal, 206),
+            ("Giveaway", Personality::Aggressive, Goal::Giveaway, -90),
+        ];
+
+        for (msg, personality, goal, expect) in cases {
+            assert_eq!(
+                expect,
+                personality.evaluate(&game, goal, game.side),
+                "{}",
+                msg
+            );
+            let flipped = personality.evaluate(&game.flip(), goal, game.flip().side);
+            assert_eq!(expect, flipped, "{} flipped", msg);
+        }
+        assert_eq!(evaluate(&game, Goal::Normal), 130);
+    }
+
+    #[test]
+    fn personality_plays_for_the_root_player() {
+        let even = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._r_._._
+                _b_._._.
+                ._._._._
+                _._._r_.
+                ._._._._
+                _._._._.
+                ._B_b_._
+            ",
+        );
+        let odd = Game {
+            side: Player::RED,
+            ..even.clone()
+        };
+
+        for personality in [
+            Personality::Balanced,
+            Personality::Aggressive,
+            Personality::Defensive,
+        ] {
+            let expect = personality.evaluate(&even, Goal::Normal, Player::BLK);
+            let actual = personality.evaluate(&odd, Goal::Normal, Player::BLK);
+            assert_eq!(expect, -actual, "{:?}", personality);
+        }
+    }
+
+    #[test]
+    fn strength_scales_with_rating() {
+        let full = Options {
+            depth: 8,
+            ..Options::default()
+        };
+        let cases = [
+            ("Below the weakest", 0, 1, Some(500), 400),
+            ("Weakest", 800, 1, Some(500), 400),
+            ("Club", 1600, 5, Some(40_500), 200),
+            ("Expert", 2200, 8, Some(1_093_500), 50),
+            ("Full", 2400, 8, None, 0),
+        ];
+
+        for (msg, rating, depth, max_nodes, noise) in cases {
+            let actual = Strength { rating }.options(full);
+            if let Some(max) = max_nodes {
+                // the captures searched in quiescence may exceed the limit a little.
+                let nodes = Searcher::new(actual).search(&Game::initial()).nodes;
+                assert!(nodes <= max + 100, "{}: {}", msg, nodes);
+            }
+            assert_eq!(
+                (depth, max_nodes, noise),
+                (actual.depth, actual.max_nodes, actual.noise),
+                "{}",
+                msg
+            );
+        }
+    }
+
+    /// play() returns the moves of a game the searcher plays against itself.
+    fn play(options: Options, plies: usize) -> Vec<String> {
+        let mut searcher = Searcher::new(options);
+        let mut game = Game::initial();
+        let mut moves = vec![];
+        for _ in 0..plies {
+            let Some(m) = searcher.search(&game).best else {
+                break;
+            };
+            moves.push(m.to_string());
+            game = game.apply(&m);
+        }
+        moves
+    }
+
+    #[test]
+    fn weak_play_is_reproducible() {
+        let weak = |seed| Options {
+            seed,
+            ..Strength { rating: 1000 }.options(Options::default())
+        };
+
+        assert_eq!(play(weak(7), 10), play(weak(7), 10));
+        let openings: Vec<Vec<String>> = (0..8).map(|seed| play(weak(seed), 2)).collect();
+        assert!(openings.iter().any(|o| *o != openings[0]), "{:?}", openings);
+    }
+}
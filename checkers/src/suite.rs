@@ -0,0 +1,66 @@
+//! suite has the fixed games which searches are compared on, by the tests and the
+//! benchmarks. it's built for tests, or with the "suite" feature.
+use crate::game::Game;
+use crate::player::Player;
+
+/// two_for_one() returns a game where black gives a pone at (3, 4) up to (2, 3), and
+/// takes two pones back by jumping from (2, 5).
+pub fn two_for_one() -> Game {
+    diagram(
+        Player::BLK,
+        r"
+            _._._._.
+            r_._._._
+            _r_._r_.
+            ._._._._
+            _._b_._.
+            ._b_._._
+            _b_._._.
+            ._._._._
+        ",
+    )
+}
+
+/// games() returns the fixed games to compare searches on, with their names.
+pub fn games() -> Vec<(&'static str, Game)> {
+    vec![
+        ("Initial position", Game::initial()),
+        ("Two for one", two_for_one()),
+        (
+            "Middle game",
+            diagram(
+                Player::BLK,
+                r"
+                    _r_r_._r
+                    r_r_._r_
+                    _._r_r_.
+                    ._r_._._
+                    _._b_b_.
+                    b_._b_._
+                    _b_b_._b
+                    b_b_._b_
+                ",
+            ),
+        ),
+        (
+            "Kings ending",
+            diagram(
+                Player::RED,
+                r"
+                    _._._._.
+                    ._._R_._
+                    _._._._.
+                    ._b_._._
+                    _B_._._.
+                    ._._r_._
+                    _._._R_.
+                    ._._._._
+                ",
+            ),
+        ),
+    ]
+}
+
+fn diagram(side: Player, s: &str) -> Game {
+    Game::from_diagram(side, None, s).expect("the suite has valid diagrams")
+}
@@ -0,0 +1,173 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::dir::Dir;
+use crate::mv::Move;
+use crate::pos::Pos;
+use crate::search::Score;
+
+/// Bound tells how the score of an Entry relates to the true score of the game.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum Bound {
+    /// the score is the true score.
+    Exact = 1,
+    /// the true score is the score or more, because the search failed high.
+    Lower = 2,
+    /// the true score is the score or less, because the search failed low.
+    Upper = 3,
+}
+
+/// Entry is what a search learned about a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    pub mv: Option<Move>, // the best move, if any move raised alpha.
+    pub score: Score,
+    pub depth: u32, // the depth the game was searched with.
+    pub bound: Bound,
+}
+
+/// Table is a transposition table keyed by Game::key(), shared by searching threads.
+/// it has a fixed number of slots, and a new entry replaces the old one in the same slot.
+/// it's lock-free: a slot stores the key xor-ed with the data, so an entry torn by
+/// concurrent writes fails the key check and is just missed.
+#[derive(Debug)]
+pub struct Table {
+    slots: Vec<Slot>,
+}
+
+#[derive(Debug, Default)]
+struct Slot {
+    check: AtomicU64, // the key xor-ed with the data.
+    data: AtomicU64,  // the encoded entry.
+}
+
+impl Table {
+    /// new() returns an empty table with 2^bits slots.
+    pub fn new(bits: u32) -> Table {
+        Table {
+            slots: (0..1 << bits).map(|_| Slot::default()).collect(),
+        }
+    }
+
+    /// get() returns the entry stored for the key.
+    pub fn get(&self, key: u64) -> Option<Entry> {
+        let slot = self.slot(key);
+        let data = slot.data.load(Ordering::Relaxed);
+        let check = slot.check.load(Ordering::Relaxed);
+        if data == 0 || check ^ data != key {
+            return None;
+        }
+        Some(decode(data))
+    }
+
+    /// put() stores the entry for the key.
+    pub fn put(&self, key: u64, entry: Entry) {
+        let slot = self.slot(key);
+        let data = encode(entry);
+        slot.data.store(data, Ordering::Relaxed);
+        slot.check.store(key ^ data, Ordering::Relaxed);
+    }
+
+    /// clear() removes every entry.
+    pub fn clear(&self) {
+        for slot in &self.slots {
+            slot.data.store(0, Ordering::Relaxed);
+            slot.check.store(0, Ordering::Relaxed);
+        }
+    }
+
+    fn slot(&self, key: u64) -> &Slot {
+        &self.slots[key as usize & (self.slots.len() - 1)]
+    }
+}
+
+/// encode() packs the entry into 64 bits: the move in the lowest 9 bits, the depth and the
+/// bound above it, and the score in the highest 32 bits. the bound is never 0, so neither
+/// is the data of an entry.
+fn encode(e: Entry) -> u64 {
+    let mv = match e.mv {
+        Some(m) => 1 << 8 | (m.jump as u64) << 7 | (m.dir as u64) << 5 | m.src.index() as u64,
+        None => 0,
+    };
+    let depth = e.depth.min(0xFF) as u64;
+    mv | depth << 16 | (e.bound as u64) << 24 | (e.score as u32 as u64) << 32
+}
+
+fn decode(data: u64) -> Entry {
+    let mv = (data & 1 << 8 != 0).then(|| Move {
+        src: Pos::raw((data & 0x1F) as u8),
+        dir: Dir::ALL[(data >> 5 & 0x3) as usize],
+        jump: data & 1 << 7 != 0,
+    });
+    let bound = match data >> 24 & 0x3 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        _ => Bound::Upper,
+    };
+    Entry {
+        mv,
+        score: (data >> 32) as u32 as Score,
+        depth: (data >> 16 & 0xFF) as u32,
+        bound,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Game;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn put_and_get() {
+        let game = Game::initial();
+        let m = game.moves().last().unwrap();
+        let next = game.apply(&m);
+        let table = Table::new(4);
+
+        let cases = [
+            Entry {
+                mv: Some(m),
+                score: -1234,
+                depth: 7,
+                bound: Bound::Lower,
+            },
+            Entry {
+                mv: None,
+                score: 56,
+                depth: 0,
+                bound: Bound::Upper,
+            },
+        ];
+
+        for entry in cases {
+            table.put(game.key(), entry);
+
+            assert_eq!(Some(entry), table.get(game.key()));
+            assert_eq!(None, table.get(next.key()));
+        }
+
+        table.clear();
+        assert_eq!(None, table.get(game.key()));
+    }
+
+    #[test]
+    fn encode_every_move() {
+        for src in 0..32 {
+            for dir in Dir::ALL {
+                for jump in [false, true] {
+                    let entry = Entry {
+                        mv: Some(Move {
+                            src: Pos::raw(src),
+                            dir,
+                            jump,
+                        }),
+                        score: Score::MIN,
+                        depth: 255,
+                        bound: Bound::Exact,
+                    };
+                    assert_eq!(entry, decode(encode(entry)));
+                }
+            }
+        }
+    }
+}
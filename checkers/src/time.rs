@@ -0,0 +1,140 @@
+use std::time::{Duration, Instant};
+
+use crate::search::{Score, PONE_VALUE};
+
+/// Clock is the time left for the side to move in a timed game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Clock {
+    pub remaining: Duration,      // the time left on the clock.
+    pub increment: Duration,      // the time added after each move.
+    pub moves_to_go: Option<u32>, // moves until the next time control. None for sudden death.
+}
+
+/// TimeManager decides how long to think about a move.
+/// the search doesn't start another iteration after the soft limit, and is stopped at the hard
+/// limit. the soft limit is extended while the best move is unstable or the score drops.
+#[derive(Debug, Clone)]
+pub struct TimeManager {
+    start: Instant,
+    soft: Duration,
+    hard: Duration,
+    extension: u32, // the percentage of the soft limit to use for the next iteration.
+}
+
+impl TimeManager {
+    /// MOVES_TO_GO is how many more moves a sudden death game is expected to last.
+    const MOVES_TO_GO: u32 = 30;
+    /// OVERHEAD is kept on the clock to send the move.
+    const OVERHEAD: Duration = Duration::from_millis(20);
+    /// HARD_RATIO is how many times the soft limit the hard limit is.
+    const HARD_RATIO: u32 = 4;
+    /// SCORE_DROP is a drop of the score which is worth thinking more.
+    const SCORE_DROP: Score = PONE_VALUE / 3;
+
+    /// new() allocates time for a move from the clock, and starts timing.
+    pub fn new(clock: &Clock) -> TimeManager {
+        let usable = clock.remaining.saturating_sub(Self::OVERHEAD);
+        let moves = clock.moves_to_go.unwrap_or(Self::MOVES_TO_GO).max(1);
+
+        let soft = (usable / moves + clock.increment * 3 / 4).min(usable);
+        let hard = (soft * Self::HARD_RATIO).min(usable);
+        TimeManager {
+            start: Instant::now(),
+            soft,
+            hard,
+            extension: 100,
+        }
+    }
+
+    /// soft() returns the time after which no more iteration is started, with extensions.
+    pub fn soft(&self) -> Duration {
+        (self.soft * self.extension / 100).min(self.hard)
+    }
+
+    /// hard() returns the time at which the search is stopped.
+    pub fn hard(&self) -> Duration {
+        self.hard
+    }
+
+    /// elapsed() returns the time since the manager was created.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// can_deepen() checks another iteration can be started.
+    pub fn can_deepen(&self) -> bool {
+        self.elapsed() < self.soft()
+    }
+
+    /// expired() checks the hard limit has passed.
+    pub fn expired(&self) -> bool {
+        self.elapsed() >= self.hard
+    }
+
+    /// update() learns the result of an iteration: whether the best move has changed, and
+    /// how much the score dropped from the previous iteration.
+    pub fn update(&mut self, changed: bool, drop: Score) {
+        self.extension = 100;
+        if changed {
+            self.extension += 50;
+        }
+        if drop >= PONE_VALUE {
+            self.extension += 100;
+        } else if drop >= Self::SCORE_DROP {
+            self.extension += 50;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn allocate_soft_and_hard_limits() {
+        let cases = [
+            ("Sudden death", ms(3020), ms(0), None, ms(100), ms(400)),
+            ("Increment", ms(3020), ms(400), None, ms(400), ms(1600)),
+            ("Moves to go", ms(1020), ms(0), Some(10), ms(100), ms(400)),
+            ("Last move", ms(1020), ms(0), Some(1), ms(1000), ms(1000)),
+            ("Low on time", ms(120), ms(1000), None, ms(100), ms(100)),
+            ("Out of time", ms(10), ms(0), None, ms(0), ms(0)),
+        ];
+
+        for (msg, remaining, increment, moves_to_go, soft, hard) in cases {
+            let tm = TimeManager::new(&Clock {
+                remaining,
+                increment,
+                moves_to_go,
+            });
+            assert_eq!((soft, hard), (tm.soft(), tm.hard()), "{}", msg);
+        }
+    }
+
+    #[test]
+    fn extend_on_instability() {
+        let cases = [
+            ("Stable", false, 0, ms(100)),
+            ("Best move changed", true, 0, ms(150)),
+            ("Score dropped a little", false, 40, ms(150)),
+            ("Score dropped a pone", false, 100, ms(200)),
+            ("Both", true, 100, ms(250)),
+            ("Score rose", true, -300, ms(150)),
+        ];
+
+        for (msg, changed, drop, soft) in cases {
+            let mut tm = TimeManager::new(&Clock {
+                remaining: ms(3020),
+                increment: ms(0),
+                moves_to_go: None,
+            });
+            tm.update(changed, drop);
+            assert_eq!(soft, tm.soft(), "{}", msg);
+        }
+    }
+}
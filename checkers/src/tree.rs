@@ -0,0 +1,447 @@
+use std::fmt::{Display, Formatter, Result};
+
+use crate::error::MoveError;
+use crate::game::{Game, Undo};
+use crate::mv::Move;
+use crate::player::Player;
+
+/// Nag is a numeric annotation glyph of PDN, like "$1" for a good move.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub struct Nag(pub u8);
+
+impl Nag {
+    pub const GOOD: Nag = Nag(1);
+    pub const MISTAKE: Nag = Nag(2);
+    pub const BRILLIANT: Nag = Nag(3);
+    pub const BLUNDER: Nag = Nag(4);
+    pub const INTERESTING: Nag = Nag(5);
+    pub const DUBIOUS: Nag = Nag(6);
+}
+
+impl Display for Nag {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "${}", self.0)
+    }
+}
+
+/// Node is a move of a GameTree and the variations following it.
+/// the first child is the main line, the others are side-lines.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+struct Node {
+    mv: Option<Move>, // the move leading to this node. it will be None only for the root.
+    children: Vec<Node>,
+    comment: String,
+    nags: Vec<Nag>,
+}
+
+/// GameTree is a game with side-lines, rooted at a start game.
+/// it has a cursor on a node, and the game at the cursor is kept up to date while moving it.
+#[derive(Debug, Clone)]
+pub struct GameTree {
+    start: Game,
+    root: Node,
+    path: Vec<usize>, // indices of children from the root to the cursor.
+    game: Game,
+    undos: Vec<Undo>,
+}
+
+impl GameTree {
+    /// new() returns a tree without any move from the game.
+    pub fn new(start: Game) -> GameTree {
+        GameTree {
+            game: start.clone(),
+            start,
+            root: Node::default(),
+            path: vec![],
+            undos: vec![],
+        }
+    }
+
+    /// start() returns the game at the root.
+    pub fn start(&self) -> &Game {
+        &self.start
+    }
+
+    /// game() returns the game at the cursor.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// ply() returns the number of moves from the root to the cursor.
+    pub fn ply(&self) -> usize {
+        self.path.len()
+    }
+
+    /// mv() returns the move leading to the cursor. it returns None at the root.
+    pub fn mv(&self) -> Option<Move> {
+        self.node().mv
+    }
+
+    /// variations() returns the moves following the cursor, the main line first.
+    pub fn variations(&self) -> Vec<Move> {
+        self.node().children.iter().filter_map(|c| c.mv).collect()
+    }
+
+    /// play() moves the cursor by the move. the move is added as a new variation if the
+    /// cursor doesn't have it yet, after the existing ones.
+    pub fn play(&mut self, m: &Move) -> std::result::Result<(), MoveError> {
+        self.game.try_apply(m)?;
+
+        let node = self.node_mut();
+        let i = match node.children.iter().position(|c| c.mv == Some(*m)) {
+            Some(i) => i,
+            None => {
+                node.children.push(Node {
+                    mv: Some(*m),
+                    ..Node::default()
+                });
+                node.children.len() - 1
+            }
+        };
+        self.enter(i);
+        Ok(())
+    }
+
+    /// forward() moves the cursor along the main line.
+    /// it returns false if there are no more moves.
+    pub fn forward(&mut self) -> bool {
+        self.variation(0)
+    }
+
+    /// variation() moves the cursor into the i-th variation.
+    /// it returns false if there is no such variation.
+    pub fn variation(&mut self, i: usize) -> bool {
+        if i >= self.node().children.len() {
+            return false;
+        }
+        self.enter(i);
+        true
+    }
+
+    /// back() moves the cursor back by a move.
+    /// it returns false at the root.
+    pub fn back(&mut self) -> bool {
+        let Some(m) = self.node().mv else {
+            return false;
+        };
+        let undo = self.undos.pop().expect("an undo for each move");
+        self.path.pop();
+        self.game.unmake(&m, &undo);
+        true
+    }
+
+    /// rewind() moves the cursor back to the root.
+    pub fn rewind(&mut self) {
+        while self.back() {}
+    }
+
+    /// promote() makes the variation at the cursor the main line of its parent.
+    /// it returns false if it's the main line already.
+    pub fn promote(&mut self) -> bool {
+        let Some(&i) = self.path.last() else {
+            return false;
+        };
+        if i == 0 {
+            return false;
+        }
+
+        self.back();
+        let children = &mut self.node_mut().children;
+        let node = children.remove(i);
+        children.insert(0, node);
+        self.enter(0);
+        true
+    }
+
+    /// delete() removes the variation at the cursor with all moves following it, and moves
+    /// the cursor back to its parent. it returns false at the root.
+    pub fn delete(&mut self) -> bool {
+        let Some(&i) = self.path.last() else {
+            return false;
+        };
+        self.back();
+        self.node_mut().children.remove(i);
+        true
+    }
+
+    /// comment() returns the comment on the move at the cursor.
+    pub fn comment(&self) -> &str {
+        &self.node().comment
+    }
+
+    /// set_comment() replaces the comment on the move at the cursor.
+    pub fn set_comment(&mut self, comment: &str) {
+        self.node_mut().comment = comment.to_string();
+    }
+
+    /// nags() returns the annotations on the move at the cursor.
+    pub fn nags(&self) -> &[Nag] {
+        &self.node().nags
+    }
+
+    /// annotate() adds the annotation to the move at the cursor, unless it has it already.
+    pub fn annotate(&mut self, nag: Nag) {
+        let nags = &mut self.node_mut().nags;
+        if !nags.contains(&nag) {
+            nags.push(nag);
+        }
+    }
+
+    /// pdn() writes the whole tree as PDN movetext with variations, like
+    /// "1. 11-15 $1 {solid} (1. 9-13) 1... 22-18".
+    /// jumps of a piece in a turn are joined into a move like "15x24x31".
+    pub fn pdn(&self) -> String {
+        let mut out = String::new();
+        let turns = TurnNode::children(&self.root, &self.start);
+        write_line(&mut out, &turns, 1, true);
+        out
+    }
+
+    fn node(&self) -> &Node {
+        self.path
+            .iter()
+            .fold(&self.root, |node, &i| &node.children[i])
+    }
+
+    fn node_mut(&mut self) -> &mut Node {
+        let mut node = &mut self.root;
+        for &i in &self.path {
+            node = &mut node.children[i];
+        }
+        node
+    }
+
+    /// enter() moves the cursor into the i-th child, which must exist.
+    fn enter(&mut self, i: usize) {
+        let m = self.node().children[i].mv.expect("children have moves");
+        let undo = self.game.make(&m);
+        self.path.push(i);
+        self.undos.push(undo);
+    }
+}
+
+/// TurnNode is a whole turn of a player for PDN, which joins moves of a jumping piece.
+struct TurnNode {
+    side: Player,
+    text: String,
+    annotations: Vec<String>,
+    children: Vec<TurnNode>,
+}
+
+impl TurnNode {
+    /// children() returns every turn following the node on the game, the main line first.
+    fn children(node: &Node, game: &Game) -> Vec<TurnNode> {
+        let mut turns = vec![];
+        for child in &node.children {
+            Self::collect(child, game, String::new(), vec![], &mut turns);
+        }
+        turns
+    }
+
+    /// collect() follows moves of the turn until it ends. the turn branches into several
+    /// turns if the jumping piece has variations.
+    fn collect(
+        node: &Node,
+        game: &Game,
+        text: String,
+        annotations: Vec<String>,
+        turns: &mut Vec<TurnNode>,
+    ) {
+        let m = node.mv.expect("children have moves");
+        let text = match text.is_empty() {
+            true => m.to_string(),
+            false => format!("{}x{}", text, m.dst()),
+        };
+        let mut annotations = annotations;
+        annotations.extend(node.nags.iter().map(|nag| nag.to_string()));
+        if !node.comment.is_empty() {
+            annotations.push(format!("{{{}}}", node.comment));
+        }
+
+        let next = game.apply(&m);
+        if next.jumping.is_none() || node.children.is_empty() {
+            turns.push(TurnNode {
+                side: game.side,
+                text,
+                annotations,
+                children: Self::children(node, &next),
+            });
+            return;
+        }
+        for child in &node.children {
+            Self::collect(child, &next, text.clone(), annotations.clone(), turns);
+        }
+    }
+}
+
+/// write_line() writes the main line of the turns, with the others as its variations.
+/// `fresh` means the line is just started or interrupted, so red's turn needs its number.
+fn write_line(out: &mut String, turns: &[TurnNode], number: u32, fresh: bool) {
+    let Some((main, rest)) = turns.split_first() else {
+        return;
+    };
+    write_turn(out, main, number, fresh);
+
+    for alt in rest {
+        out.push_str(" (");
+        write_turn(out, alt, number, true);
+        write_line(out, &alt.children, next_number(alt, number), false);
+        out.push(')');
+    }
+
+    let fresh = !rest.is_empty() || !main.annotations.is_empty();
+    write_line(out, &main.children, next_number(main, number), fresh);
+}
+
+/// write_turn() writes the turn with its number and annotations.
+fn write_turn(out: &mut String, turn: &TurnNode, number: u32, fresh: bool) {
+    match turn.side {
+        Player::BLK => push_token(out, &format!("{}.", number)),
+        Player::RED if fresh => push_token(out, &format!("{}...", number)),
+        Player::RED => (),
+    }
+    push_token(out, &turn.text);
+    for annotation in &turn.annotations {
+        push_token(out, annotation);
+    }
+}
+
+/// next_number() returns the number of the turn after the turn, which increases after red.
+fn next_number(turn: &TurnNode, number: u32) -> u32 {
+    match turn.side {
+        Player::BLK => number,
+        Player::RED => number + 1,
+    }
+}
+
+fn push_token(out: &mut String, token: &str) {
+    if !out.is_empty() && !out.ends_with('(') {
+        out.push(' ');
+    }
+    out.push_str(token);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::testutil;
+    use crate::pos::Pos;
+    use pretty_assertions::assert_eq;
+
+    /// play() plays the moves given in squares from the cursor.
+    fn play(tree: &mut GameTree, moves: &[(u8, u8)]) {
+        for &(src, dst) in moves {
+            let m = testutil::step(tree.game(), src, dst);
+            tree.play(&m).unwrap();
+        }
+    }
+
+    #[test]
+    fn navigate_variations() {
+        let mut tree = GameTree::new(Game::initial());
+        play(&mut tree, &[(11, 15), (22, 18)]);
+        tree.rewind();
+        play(&mut tree, &[(9, 13)]);
+
+        assert_eq!(1, tree.ply());
+        tree.back();
+        assert_eq!(
+            vec![
+                testutil::step(&Game::initial(), 11, 15),
+                testutil::step(&Game::initial(), 9, 13)
+            ],
+            tree.variations()
+        );
+
+        assert_eq!(true, tree.variation(1));
+        assert_eq!(true, tree.promote());
+        assert_eq!(false, tree.promote());
+        tree.rewind();
+        assert_eq!(true, tree.forward());
+        assert_eq!(testutil::step(&Game::initial(), 9, 13), tree.mv().unwrap());
+        assert_eq!(false, tree.forward());
+
+        assert_eq!(true, tree.delete());
+        assert_eq!(&Game::initial(), tree.game());
+        assert_eq!(true, tree.forward());
+        assert_eq!(true, tree.forward());
+        assert_eq!(
+            Some(Pos::from_square(22).unwrap()),
+            tree.mv().map(|m| m.src)
+        );
+        tree.rewind();
+        assert_eq!(false, tree.back());
+        assert_eq!(&Game::initial(), tree.game());
+    }
+
+    #[test]
+    fn play_rejects_illegal_moves() {
+        let mut tree = GameTree::new(Game::initial());
+        let m = Move {
+            src: Pos::from_square(22).unwrap(),
+            dir: crate::dir::Dir::BackwardRight,
+            jump: false,
+        };
+
+        assert_eq!(Err(MoveError::WrongSide), tree.play(&m));
+        assert_eq!(0, tree.ply());
+        assert_eq!(Vec::<Move>::new(), tree.variations());
+    }
+
+    #[test]
+    fn pdn_writes_variations_and_annotations() {
+        let mut tree = GameTree::new(Game::initial());
+        play(&mut tree, &[(11, 15)]);
+        tree.annotate(Nag::GOOD);
+        tree.set_comment("the old fourteenth");
+        play(&mut tree, &[(23, 19), (8, 11)]);
+        tree.back();
+        play(&mut tree, &[(9, 14)]);
+        tree.rewind();
+        play(&mut tree, &[(9, 13), (22, 18)]);
+        tree.annotate(Nag::DUBIOUS);
+
+        assert_eq!(
+            "1. 11-15 $1 {the old fourteenth} (1. 9-13 22-18 $6) 1... 23-19 2. 8-11 (2. 9-14)",
+            tree.pdn()
+        );
+    }
+
+    #[test]
+    fn pdn_joins_jumps_in_a_turn() {
+        let start = testutil::game(
+            Player::BLK,
+            None,
+            r"
+                _._._._.
+                ._._._._
+                _._r_r_.
+                ._._._._
+                _._r_._.
+                ._b_._._
+                _._._._.
+                ._._._._
+            ",
+        );
+        let mut tree = GameTree::new(start);
+        let first = tree.game().moves().next().unwrap();
+        tree.play(&first).unwrap();
+        let branches: Vec<Move> = tree.game().moves().collect();
+        for m in &branches {
+            tree.play(m).unwrap();
+            tree.back();
+        }
+
+        assert_eq!(2, branches.len());
+        assert_eq!(
+            format!(
+                "1. {}x{} (1. {}x{})",
+                first,
+                branches[0].dst(),
+                first,
+                branches[1].dst()
+            ),
+            tree.pdn()
+        );
+    }
+}
@@ -0,0 +1,73 @@
+use derive_more::{
+    BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Shl, ShlAssign, Shr,
+    ShrAssign,
+};
+
+use crate::turkish::pos::Pos;
+
+/// Board is a bitboard over all 64 squares; bit (y << 3) + x stands for Pos::new(x, y).
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    Hash,
+    PartialEq,
+    Eq,
+    BitAnd,
+    BitAndAssign,
+    BitOr,
+    BitOrAssign,
+    BitXor,
+    BitXorAssign,
+    Shl,
+    ShlAssign,
+    Shr,
+    ShrAssign,
+    Not,
+)]
+pub struct Board(u64);
+
+impl Board {
+    pub const fn new(bits: u64) -> Board {
+        Board(bits)
+    }
+
+    pub const fn empty() -> Board {
+        Board(0)
+    }
+
+    /// count() returns the number of active positions.
+    pub fn count(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    /// actives() iterate all active positions.
+    pub fn actives(self) -> impl Iterator<Item = Pos> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let shift = bits.trailing_zeros() as u8;
+            bits &= bits - 1;
+            Some(Pos::raw(shift))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn actives_enumerates_specified_positions() {
+        let target = Board::new(1 | 1 << 9 | 1 << 63);
+
+        let actual: Vec<Pos> = target.actives().collect();
+        let expect = vec![Pos::raw(0), Pos::raw(9), Pos::raw(63)];
+
+        assert_eq!(expect, actual);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::player::Player;
+use crate::turkish::board::Board;
+use crate::turkish::pos::Pos;
+
+/// Dir is an orthogonal direction; Forward is the direction black men advance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Dir {
+    Forward,
+    Backward,
+    Left,
+    Right,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Forward, Dir::Backward, Dir::Left, Dir::Right];
+
+    /// FILE_LEFT is the most left file, which can't move to left anymore.
+    const FILE_LEFT: Board = Board::new(0x8080_8080_8080_8080);
+    /// FILE_RIGHT is the most right file, which can't move to right anymore.
+    const FILE_RIGHT: Board = Board::new(0x0101_0101_0101_0101);
+
+    /// valid() checks the piece can move to this direction.
+    /// men move forward or sideways, kings move to every direction.
+    pub fn valid(self, p: Player, king: bool) -> bool {
+        match (self, p, king) {
+            (_, _, true) => true,
+            (Self::Left | Self::Right, _, _) => true,
+            (Self::Forward, Player::BLK, _) => true,
+            (Self::Backward, Player::RED, _) => true,
+            (_, _, _) => false,
+        }
+    }
+
+    /// apply() moves every piece of target one cell to this direction.
+    #[inline(always)]
+    pub fn apply(self, target: Board) -> Board {
+        match self {
+            Self::Forward => target << 8,
+            Self::Backward => target >> 8,
+            Self::Left => (target & !Self::FILE_LEFT) << 1,
+            Self::Right => (target & !Self::FILE_RIGHT) >> 1,
+        }
+    }
+
+    /// step() returns the next cell to this direction, or None at the edge of the board.
+    pub fn step(self, pos: Pos) -> Option<Pos> {
+        self.apply(pos.board()).actives().next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn step_stops_at_edges() {
+        let cases = [
+            ("Forward from top", Dir::Forward, (3, 0), None),
+            ("Backward from bottom", Dir::Backward, (3, 7), None),
+            ("Left from most left", Dir::Left, (0, 4), None),
+            ("Right from most right", Dir::Right, (7, 4), None),
+            ("Forward from center", Dir::Forward, (3, 4), Some((3, 3))),
+            ("Backward from center", Dir::Backward, (3, 4), Some((3, 5))),
+            ("Left from center", Dir::Left, (3, 4), Some((2, 4))),
+            ("Right from center", Dir::Right, (3, 4), Some((4, 4))),
+        ];
+
+        for (msg, dir, (x, y), exp) in cases {
+            let expect = exp.and_then(|(x, y)| Pos::graphical(x, y));
+            let actual = dir.step(Pos::graphical(x, y).unwrap());
+            assert_eq!(expect, actual, "{}", msg);
+        }
+    }
+}
@@ -0,0 +1,458 @@
+use crate::player::Player;
+use crate::turkish::board::Board;
+use crate::turkish::dir::Dir;
+use crate::turkish::mv::Move;
+use crate::turn::Sequence;
+
+#[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
+pub struct Game {
+    pub side: Player, // which side is now considering next move.
+    pub red: Board,   // 1st player piece existence.
+    pub blk: Board,   // 2nd player piece existence.
+    pub king: Board,  // the piece is king or man.
+}
+
+impl Game {
+    /// PROMOTION_MASK is mask for man's promotion.
+    const PROMOTION_MASK: Board = Board::new(0xFF00_0000_0000_00FF);
+
+    /// initial() returns the starting position, 16 men for each player on the 2nd and 3rd rows.
+    pub fn initial() -> Game {
+        Game {
+            side: Player::BLK,
+            red: Board::new(0x00FF_FF00_0000_0000),
+            blk: Board::new(0x0000_0000_00FF_FF00),
+            king: Board::empty(),
+        }
+    }
+
+    /// moves() enumerates all next move candidates.
+    /// capturing is mandatory, and only the sequences capturing the most pieces are allowed.
+    pub fn moves(&self) -> impl Iterator<Item = Move> {
+        let jumps = self.jumps();
+        if jumps.is_empty() {
+            self.steps().into_iter()
+        } else {
+            jumps.into_iter()
+        }
+    }
+
+    pub fn apply(&self, m: &Move) -> Game {
+        let mut g = self.clone();
+        let king = &mut g.king;
+        let (slf, opp) = match self.side {
+            Player::BLK => (&mut g.blk, &mut g.red),
+            Player::RED => (&mut g.red, &mut g.blk),
+        };
+
+        let is_king = m.src.is(*king);
+
+        let src_mask = !m.src.board();
+        *slf &= src_mask;
+        *king &= src_mask;
+
+        *opp &= !m.captured;
+        *king &= !m.captured;
+
+        // men are crowned only when the whole sequence finishes on the last row.
+        let dst_mask = m.dst().board();
+        let is_promotion = (dst_mask & Self::PROMOTION_MASK) != Board::empty();
+
+        *slf |= dst_mask;
+        if is_king || is_promotion {
+            *king |= dst_mask;
+        }
+
+        g.side = !g.side;
+        g
+    }
+
+    /// winner() returns which player is winner.
+    /// a player loses when it has no piece or no move on its turn.
+    /// if there are no winner, it returns None.
+    pub fn winner(&self) -> Option<Player> {
+        match () {
+            _ if self.blk == Board::empty() => Some(Player::RED),
+            _ if self.red == Board::empty() => Some(Player::BLK),
+            _ if self.moves().next().is_none() => Some(!self.side),
+            _ => None,
+        }
+    }
+
+    fn own(&self) -> (Board, Board) {
+        match self.side {
+            Player::BLK => (self.blk, self.red),
+            Player::RED => (self.red, self.blk),
+        }
+    }
+
+    fn steps(&self) -> Vec<Move> {
+        let (slf, _) = self.own();
+        let occupied = self.red | self.blk;
+
+        let mut steps = vec![];
+        for src in slf.actives() {
+            let king = src.is(self.king);
+            for dir in Dir::ALL {
+                if !dir.valid(self.side, king) {
+                    continue;
+                }
+
+                let mut cur = dir.step(src);
+                while let Some(pos) = cur {
+                    if pos.is(occupied) {
+                        break;
+                    }
+                    steps.push(Move {
+                        src,
+                        path: vec![pos],
+                        captured: Board::empty(),
+                    });
+                    if !king {
+                        break;
+                    }
+                    cur = dir.step(pos);
+                }
+            }
+        }
+
+        steps
+    }
+
+    fn jumps(&self) -> Vec<Move> {
+        let (slf, opp) = self.own();
+
+        let mut found = vec![];
+        for src in slf.actives() {
+            let king = src.is(self.king);
+            let dirs: Vec<Dir> = Dir::ALL
+                .into_iter()
+                .filter(|dir| dir.valid(self.side, king))
+                .collect();
+            // kings fly over empty cells before and after the capture.
+            Sequence::search(src, &dirs, king, self.red | self.blk, opp, &mut found);
+        }
+
+        let most = found.iter().map(|m| m.captured.count()).max();
+        found.retain(|m| Some(m.captured.count()) == most);
+        found
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod testutil {
+    use super::*;
+
+    use crate::player::Player;
+    use crate::turkish::pos::Pos;
+    use unindent::unindent;
+
+    pub fn game(side: Player, s: &str) -> Game {
+        let s = unindent(s);
+        let mut game = Game {
+            side,
+            ..Default::default()
+        };
+
+        let lines = s.split("\n");
+        for (y, line) in lines.enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let pos = Pos::graphical(x as u8, y as u8).and_then(|pos| match c {
+                    'b' => Some((Player::BLK, false, pos)),
+                    'B' => Some((Player::BLK, true, pos)),
+                    'r' => Some((Player::RED, false, pos)),
+                    'R' => Some((Player::RED, true, pos)),
+                    _ => None,
+                });
+                match pos {
+                    Some((Player::BLK, _, pos)) => game.blk |= pos.board(),
+                    Some((Player::RED, _, pos)) => game.red |= pos.board(),
+                    _ => (),
+                };
+                if let Some((_, true, pos)) = pos {
+                    game.king |= pos.board();
+                }
+            }
+        }
+
+        game
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::turkish::pos::Pos;
+    use pretty_assertions::assert_eq;
+
+    fn g(x: u8, y: u8) -> Pos {
+        Pos::graphical(x, y).unwrap()
+    }
+
+    fn step(src: Pos, dst: Pos) -> Move {
+        Move {
+            src,
+            path: vec![dst],
+            captured: Board::empty(),
+        }
+    }
+
+    fn sorted(moves: impl IntoIterator<Item = Move>) -> Vec<(Pos, Vec<Pos>, Board)> {
+        let mut v: Vec<_> = moves
+            .into_iter()
+            .map(|m| (m.src, m.path, m.captured))
+            .collect();
+        v.sort_by_key(|(src, path, _)| (*src, path.clone()));
+        v
+    }
+
+    #[test]
+    fn initial_position_moves_only_front_men() {
+        let game = Game::initial();
+
+        let expect = sorted((0..8).map(|x| step(g(x, 5), g(x, 4))));
+        let actual = sorted(game.moves());
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn moves_enumerate_all_move_candidates() {
+        let cases = [
+            (
+                "black men move forward and sideways",
+                Player::BLK,
+                r"
+                    ........
+                    ........
+                    ........
+                    ........
+                    ...b....
+                    ........
+                    ........
+                    ........
+                ",
+                vec![
+                    step(g(3, 4), g(3, 3)),
+                    step(g(3, 4), g(2, 4)),
+                    step(g(3, 4), g(4, 4)),
+                ],
+            ),
+            (
+                "red men move forward to the bottom",
+                Player::RED,
+                r"
+                    ........
+                    ........
+                    ........
+                    ........
+                    r.......
+                    ........
+                    ........
+                    ........
+                ",
+                vec![step(g(0, 4), g(0, 5)), step(g(0, 4), g(1, 4))],
+            ),
+            (
+                "kings fly to every direction",
+                Player::BLK,
+                r"
+                    ........
+                    ........
+                    ........
+                    ........
+                    ...B....
+                    ........
+                    ........
+                    ........
+                ",
+                (0..8)
+                    .filter(|&v| v != 3)
+                    .map(|x| step(g(3, 4), g(x, 4)))
+                    .chain((0..8).filter(|&v| v != 4).map(|y| step(g(3, 4), g(3, y))))
+                    .collect(),
+            ),
+        ];
+
+        for (msg, side, game, expects) in cases {
+            let game = testutil::game(side, game);
+
+            let expect = sorted(expects);
+            let actual = sorted(game.moves());
+            assert_eq!(expect, actual, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn moves_choose_maximum_capture() {
+        let game = testutil::game(
+            Player::BLK,
+            r"
+                ........
+                ........
+                ........
+                ...r....
+                ........
+                ...r....
+                ..rb....
+                ........
+            ",
+        );
+
+        let expect = sorted([Move {
+            src: g(3, 6),
+            path: vec![g(3, 4), g(3, 2)],
+            captured: g(3, 5).board() | g(3, 3).board(),
+        }]);
+        let actual = sorted(game.moves());
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn kings_capture_from_distance() {
+        let game = testutil::game(
+            Player::BLK,
+            r"
+                ........
+                ........
+                ........
+                ........
+                r.......
+                ........
+                ........
+                B.......
+            ",
+        );
+
+        let expect = sorted((0..4).map(|y| Move {
+            src: g(0, 7),
+            path: vec![g(0, y)],
+            captured: g(0, 4).board(),
+        }));
+        let actual = sorted(game.moves());
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn captured_pieces_block_until_sequence_finishes() {
+        // the 4th capture on (2, 1) would be reachable if (2, 3) were removed at once.
+        let game = testutil::game(
+            Player::BLK,
+            r"
+                ........
+                ..r.....
+                ........
+                B.r.....
+                ........
+                ....r...
+                ...r....
+                ........
+            ",
+        );
+
+        let captured = g(2, 3).board() | g(4, 5).board() | g(3, 6).board();
+        let expect = sorted((0..3).map(|x| Move {
+            src: g(0, 3),
+            path: vec![g(4, 3), g(4, 6), g(x, 6)],
+            captured,
+        }));
+        let actual = sorted(game.moves());
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn apply_removes_captured_and_promotes() {
+        let before = testutil::game(
+            Player::BLK,
+            r"
+                ........
+                ...r....
+                ...b....
+                ........
+                ........
+                ........
+                ........
+                .......r
+            ",
+        );
+        let expect = testutil::game(
+            Player::RED,
+            r"
+                ...B....
+                ........
+                ........
+                ........
+                ........
+                ........
+                ........
+                .......r
+            ",
+        );
+
+        let m = before.moves().next().unwrap();
+        let actual = before.apply(&m);
+
+        assert_eq!(expect, actual);
+    }
+
+    #[test]
+    fn winner_is_decided_by_pieces_and_moves() {
+        let cases = [
+            (
+                "Game is continue normally",
+                Player::BLK,
+                r"
+                    ........
+                    ...r....
+                    ........
+                    ........
+                    ........
+                    ........
+                    ...b....
+                    ........
+                ",
+                None,
+            ),
+            (
+                "Game only have black pieces",
+                Player::RED,
+                r"
+                    ........
+                    ........
+                    ........
+                    ........
+                    ........
+                    ........
+                    ...b....
+                    ........
+                ",
+                Some(Player::BLK),
+            ),
+            (
+                "Black is blocked",
+                Player::BLK,
+                r"
+                    ........
+                    ........
+                    ........
+                    ........
+                    r.......
+                    r.......
+                    brr.....
+                    ........
+                ",
+                Some(Player::RED),
+            ),
+        ];
+
+        for (msg, side, game, expected) in cases {
+            let game = testutil::game(side, game);
+            let actual = game.winner();
+
+            assert_eq!(expected, actual, "{}", msg);
+        }
+    }
+}
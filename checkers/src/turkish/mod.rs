@@ -0,0 +1,12 @@
+//! Turkish draughts, played orthogonally on every square of an 8x8 board.
+pub mod board;
+pub mod dir;
+pub mod game;
+pub mod mv;
+pub mod pos;
+
+pub use self::board::*;
+pub use self::dir::*;
+pub use self::game::*;
+pub use self::mv::*;
+pub use self::pos::*;
@@ -0,0 +1,6 @@
+use crate::turkish::board::Board;
+use crate::turkish::pos::Pos;
+use crate::turn::Turn;
+
+/// Move is a whole turn: a single step, or a complete capture sequence.
+pub type Move = Turn<Pos, Board>;
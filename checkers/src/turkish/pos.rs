@@ -0,0 +1,118 @@
+use crate::turkish::board::Board;
+use crate::turkish::dir::Dir;
+use crate::turn::Cell;
+use std::fmt::{Debug, Formatter, Result};
+
+#[derive(Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Pos(u8);
+
+impl Pos {
+    pub fn raw(v: u8) -> Pos {
+        Pos(v)
+    }
+
+    /// new() creates new Pos instance by internal position expression.
+    pub fn new(x: u8, y: u8) -> Pos {
+        debug_assert!(x < 8);
+        debug_assert!(y < 8);
+
+        Pos((y << 3) + x)
+    }
+
+    /// graphical() converts graphical position into internal Pos.
+    /// every cell is used on this board, so it only returns None for positions outside of it.
+    pub fn graphical(x: u8, y: u8) -> Option<Pos> {
+        if 8 <= x || 8 <= y {
+            return None;
+        }
+
+        Some(Pos::new(7 - x, 7 - y))
+    }
+
+    /// x returns internal position-x for this Pos.
+    pub fn x(self) -> u8 {
+        self.0 & 0x7
+    }
+
+    /// y returns internal position-y for this Pos.
+    pub fn y(self) -> u8 {
+        self.0 >> 3
+    }
+
+    /// gx returns graphical position-x for this Pos.
+    pub fn gx(self) -> u8 {
+        7 - self.x()
+    }
+
+    /// gy returns graphical position-y for this Pos.
+    pub fn gy(self) -> u8 {
+        7 - self.y()
+    }
+
+    /// board returns bitboard's bit for this position.
+    pub fn board(self) -> Board {
+        Board::new(1 << self.0)
+    }
+
+    /// is() checks position of target Board has a bit or not.
+    pub fn is(self, props: Board) -> bool {
+        (props & self.board()) != Board::empty()
+    }
+}
+
+impl Debug for Pos {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        f.debug_tuple("Pos")
+            .field(&self.x())
+            .field(&self.y())
+            .finish()
+    }
+}
+
+impl Cell for Pos {
+    type Board = Board;
+    type Dir = Dir;
+
+    fn board(self) -> Board {
+        Pos::board(self)
+    }
+
+    fn step(self, dir: Dir) -> Option<Pos> {
+        dir.step(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn convert_graphical_position() {
+        let cases = [
+            ((0, 0), Some(63), "Left-Top"),
+            ((7, 0), Some(56), "Right-Top"),
+            ((0, 7), Some(7), "Left-Bottom"),
+            ((7, 7), Some(0), "Right-Bottom"),
+            ((3, 4), Some(28), "Center cell"),
+            ((8, 0), None, "Outside of right side"),
+            ((0, 8), None, "Outside of bottom side"),
+        ];
+
+        for ((x, y), exp, msg) in cases {
+            let expect = exp.map(Pos);
+            let actual = Pos::graphical(x, y);
+            assert_eq!(expect, actual, "{}", msg);
+        }
+    }
+
+    #[test]
+    fn graphical_round_trip() {
+        for y in 0..8 {
+            for x in 0..8 {
+                let pos = Pos::graphical(x, y).unwrap();
+                assert_eq!((x, y), (pos.gx(), pos.gy()));
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+use std::fmt::{Display, Formatter, Result};
+use std::ops::{BitAnd, BitOr, Not};
+
+use crate::board::Board;
+use crate::dir::Dir;
+use crate::pos::Pos;
+
+/// Turn is a whole turn under some Rules: a single step, or a complete capture sequence.
+/// Turkish draughts shares it over its own board as turkish::Move.
+///
+/// Game::moves() and Game::apply() play a capture sequence one hop at a time under the
+/// American rules, which is what the search and the game tree use. Game::turns() and
+/// Game::play() play whole turns under any Rules, for variants and move lists to show.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Turn<P = Pos, B = Board> {
+    pub src: P,
+    pub path: Vec<P>, // every landing cell in order. a simple step has only one.
+    pub captured: B,  // captured pieces, which are removed only after the whole sequence.
+}
+
+impl Display for Turn {
+    /// fmt() writes the turn in standard notation, like "11-15" or "15x24x31".
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let sep = if self.jump() { 'x' } else { '-' };
+        write!(f, "{}", self.src)?;
+        for pos in &self.path {
+            write!(f, "{}{}", sep, pos)?;
+        }
+        Ok(())
+    }
+}
+
+impl<P: Copy, B: Default + PartialEq> Turn<P, B> {
+    /// dst() return destination position of this turn.
+    pub fn dst(&self) -> P {
+        // a turn always has at least one landing cell.
+        self.path[self.path.len() - 1]
+    }
+
+    /// jump() checks this turn captures any piece.
+    pub fn jump(&self) -> bool {
+        self.captured != B::default()
+    }
+}
+
+/// Cell is a cell of a board which capture sequences are searched on.
+pub(crate) trait Cell: Copy {
+    type Board: Copy
+        + Default
+        + PartialEq
+        + BitAnd<Output = Self::Board>
+        + BitOr<Output = Self::Board>
+        + Not<Output = Self::Board>;
+    type Dir: Copy;
+
+    /// board() returns the bitboard with only this cell.
+    fn board(self) -> Self::Board;
+
+    /// step() returns the next cell to the direction, or None at the edge of the board.
+    fn step(self, dir: Self::Dir) -> Option<Self>;
+
+    /// is() checks the cell is in the board.
+    fn is(self, b: Self::Board) -> bool {
+        (b & self.board()) != Self::Board::default()
+    }
+}
+
+impl Cell for Pos {
+    type Board = Board;
+    type Dir = Dir;
+
+    fn board(self) -> Board {
+        Pos::board(self)
+    }
+
+    fn step(self, dir: Dir) -> Option<Pos> {
+        dir.step(self)
+    }
+}
+
+/// Sequence searches every capture sequence of a single piece.
+/// captured pieces stay on the board until the sequence finishes,
+/// so they block the way and can't be captured twice.
+pub(crate) struct Sequence<'a, P: Cell> {
+    src: P,
+    dirs: &'a [P::Dir], // the directions the piece captures to.
+    flying: bool,       // the piece passes over empty cells before and after the capture.
+    occupied: P::Board,
+    opp: P::Board,
+    path: Vec<P>,
+    found: &'a mut Vec<Turn<P, P::Board>>,
+}
+
+impl<'a, P: Cell> Sequence<'a, P> {
+    /// search() appends every capture sequence of the piece on src to found.
+    pub(crate) fn search(
+        src: P,
+        dirs: &'a [P::Dir],
+        flying: bool,
+        occupied: P::Board,
+        opp: P::Board,
+        found: &'a mut Vec<Turn<P, P::Board>>,
+    ) {
+        let mut seq = Sequence {
+            src,
+            dirs,
+            flying,
+            // the moving piece leaves its cell, so it can pass through there again.
+            occupied: occupied & !src.board(),
+            opp,
+            path: vec![],
+            found,
+        };
+        seq.extend(src, P::Board::default());
+    }
+
+    fn extend(&mut self, at: P, captured: P::Board) {
+        let mut extended = false;
+
+        for &dir in self.dirs {
+            // flying pieces pass over empty cells until they meet the piece to capture.
+            let mut mid = at.step(dir);
+            while let (true, Some(pos)) = (self.flying, mid) {
+                if pos.is(self.occupied) {
+                    break;
+                }
+                mid = pos.step(dir);
+            }
+            let mid = match mid {
+                Some(mid) if mid.is(self.opp & !captured) => mid,
+                _ => continue,
+            };
+
+            let mut land = mid.step(dir);
+            while let Some(pos) = land {
+                if pos.is(self.occupied) {
+                    break;
+                }
+                self.path.push(pos);
+                self.extend(pos, captured | mid.board());
+                self.path.pop();
+                extended = true;
+
+                if !self.flying {
+                    break;
+                }
+                land = pos.step(dir);
+            }
+        }
+
+        if !extended && captured != P::Board::default() {
+            self.found.push(Turn {
+                src: self.src,
+                path: self.path.clone(),
+                captured,
+            });
+        }
+    }
+}
@@ -43,6 +43,7 @@ impl Checkers {
                 blk: Board::new(0b0000_0000_0000_0000_0000_0000_1111_1111),
                 red: Board::new(0b1111_1111_1111_0000_0010_0000_0000_0000),
                 king: Board::new(0b0000_1000_0000_0000_0000_0000_0000_0000),
+                passed: false,
             },
         }
     }
@@ -77,12 +78,9 @@ impl Checkers {
             }
             (Mode::SelectingDestCell { src }, true, true) => {
                 // TODO: find suitable move for this moving.
-                match moves.iter().find(|m| m.src == src && m.dst() == pos) {
-                    Some(m) => {
-                        self.game = self.game.apply(m);
-                        self.mode = Mode::SelectingMovePiece;
-                    }
-                    None => (),
+                if let Some(m) = moves.iter().find(|m| m.src == src && m.dst() == pos) {
+                    self.game = self.game.apply(m);
+                    self.mode = Mode::SelectingMovePiece;
                 }
             }
             (_, _, _) => (),